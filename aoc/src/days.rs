@@ -0,0 +1,15 @@
+//! Links the library of every day, so their `#[aoc_parse]` and `#[aoc_part]`
+//! functions are registered in this binary.
+
+use day01 as _;
+use day02 as _;
+use day03 as _;
+use day04 as _;
+use day05 as _;
+use day06 as _;
+use day07 as _;
+use day08 as _;
+use day09 as _;
+use day10 as _;
+use day11 as _;
+use day12 as _;
@@ -0,0 +1,309 @@
+mod days;
+
+use common::answers::{self, Answers, Check};
+use common::input;
+use common::registry::{self, DayRun, Part};
+use common::timing::{self, Output};
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: aoc run <DAY>... [--example] [--input <PATH>] [--input-dir <DIR>]
+       aoc run --all [--example] [--input-dir <DIR>]
+       aoc list
+
+Options:
+  --example           Read the example input (XX.example.txt, or XX.exampleN.txt for
+                      part N when it exists) instead of the puzzle input
+  --input <PATH>      Read the input of a single day from PATH (`-` for stdin)
+  --input-dir <DIR>   Read inputs from DIR/XX.txt instead of the workspace `input/`
+
+Puzzle answers are checked against DIR/answers.toml when it exists.";
+
+const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../input");
+
+struct Options {
+    days: Vec<u8>,
+    input: Option<PathBuf>,
+    input_dir: PathBuf,
+    example: bool,
+}
+
+impl Options {
+    /// Input of `part` of `day`. Later parts use their own example
+    /// (`XX.example2.txt`, ...) when there is one.
+    fn input_path(&self, day: u8, part: u8) -> PathBuf {
+        if let Some(input) = &self.input {
+            return input.clone();
+        }
+        if !self.example {
+            return self.input_dir.join(format!("{day:02}.txt"));
+        }
+        let own = self.input_dir.join(format!("{day:02}.example{part}.txt"));
+        if part > 1 && own.is_file() {
+            own
+        } else {
+            self.input_dir.join(format!("{day:02}.example.txt"))
+        }
+    }
+
+    /// Known answers, only used for the puzzle inputs of the input directory.
+    fn answers(&self) -> Result<Answers, String> {
+        if self.example || self.input.is_some() {
+            return Ok(Answers::default());
+        }
+        Answers::load(&self.input_dir.join(answers::FILE_NAME))
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "list") {
+        list_parts();
+        return ExitCode::SUCCESS;
+    }
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{msg}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answers = match options.answers() {
+        Ok(answers) => answers,
+        Err(msg) => {
+            eprintln!("{msg}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rows = Vec::with_capacity(options.days.len());
+    let mut failed = false;
+    let json = Output::from_env() == Output::Json;
+    for &day in &options.days {
+        let row = match run_day(&options, day) {
+            Ok(run) => {
+                if json {
+                    report_json(day, &run);
+                }
+                let check = check_answers(&answers, day, &run);
+                failed |= check == "MISMATCH" || run.parts.iter().any(|p| p.answer.is_err());
+                Row::from_run(day, &run, check)
+            }
+            Err(msg) => {
+                failed = true;
+                Row::error(day, msg)
+            }
+        };
+        rows.push(row);
+    }
+
+    print_table(&rows);
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs the registered parts of `day`, parsing each of their inputs once.
+fn run_day(options: &Options, day: u8) -> Result<DayRun, String> {
+    let mut inputs: Vec<(PathBuf, Vec<&'static Part>)> = Vec::new();
+    for part in registry::day_parts(day) {
+        let path = options.input_path(day, part.part);
+        match inputs.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, parts)) => parts.push(part),
+            None => inputs.push((path, vec![part])),
+        }
+    }
+
+    let mut run = DayRun {
+        parse_time: Duration::ZERO,
+        parts: Vec::new(),
+    };
+    for (path, parts) in inputs {
+        let input =
+            input::read(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let input_run =
+            registry::run(day, &input, &parts).map_err(|e| format!("invalid input: {e}"))?;
+        run.parse_time += input_run.parse_time;
+        run.parts.extend(input_run.parts);
+    }
+    Ok(run)
+}
+
+/// Summarises the answer checks of `day` for the table, printing mismatches and
+/// failed parts on stderr.
+fn check_answers(answers: &Answers, day: u8, run: &DayRun) -> &'static str {
+    let mut summary = "";
+    for part in &run.parts {
+        let (part, answer) = match &part.answer {
+            Ok(answer) => (part.part.part, answer),
+            Err(e) => {
+                eprintln!("Day {day:02} part {}: {e}", part.part.part);
+                continue;
+            }
+        };
+        match answers.check(day, part, answer) {
+            Check::Unknown => {}
+            Check::Ok => {
+                if summary.is_empty() {
+                    summary = "OK";
+                }
+            }
+            check @ Check::Mismatch { .. } => {
+                eprintln!("Day {day:02} part {part}: {answer} {check}");
+                summary = "MISMATCH";
+            }
+        }
+    }
+    summary
+}
+
+/// Prints the parts registered with `#[aoc_part]`, one line per day.
+fn list_parts() {
+    let parts = registry::parts();
+    for day in parts.chunk_by(|a, b| a.day == b.day) {
+        let names: Vec<String> = day
+            .iter()
+            .map(|p| format!("part {} ({})", p.part, p.name))
+            .collect();
+        println!("Day {:02}: {}", day[0].day, names.join(", "));
+    }
+}
+
+/// Writes the phase timings of `day` as JSON lines on stderr, see [`timing::Output::Json`].
+fn report_json(day: u8, run: &DayRun) {
+    timing::report(Some(day), "parse", &mut [run.parse_time]);
+    for part in &run.parts {
+        let phase = format!("part{}", part.part.part);
+        timing::report(Some(day), &phase, &mut [part.time]);
+    }
+    timing::report(Some(day), "total", &mut [run.total_time()]);
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err("Missing command".to_string());
+    };
+    if command != "run" {
+        return Err(format!("Unknown command: {command}"));
+    }
+
+    let mut options = Options {
+        days: Vec::new(),
+        input: None,
+        input_dir: PathBuf::from(INPUT_DIR),
+        example: false,
+    };
+    let mut all = false;
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--example" => options.example = true,
+            "--input" => options.input = Some(option_value(arg, rest.next())?.into()),
+            "--input-dir" => options.input_dir = option_value(arg, rest.next())?.into(),
+            _ => match arg.parse() {
+                Ok(day) if registry::find_day(day).is_some() => options.days.push(day),
+                _ => return Err(format!("Invalid day: {arg}")),
+            },
+        }
+    }
+
+    if all {
+        options.days = registry::days().iter().map(|d| d.day).collect();
+    }
+    if options.days.is_empty() {
+        return Err("No days selected".to_string());
+    }
+    if options.input.is_some() && options.days.len() > 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    Ok(options)
+}
+
+fn option_value<'a>(name: &str, value: Option<&'a String>) -> Result<&'a str, String> {
+    value
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing value for {name}"))
+}
+
+const COLUMNS: usize = 8;
+
+struct Row {
+    cells: [String; COLUMNS],
+}
+
+impl Row {
+    fn from_run(day: u8, run: &DayRun, check: &str) -> Self {
+        // Days without a second part show `-`.
+        let part = |n| run.parts.iter().find(|p| p.part.part == n);
+        let answer = |n| match part(n).map(|p| &p.answer) {
+            Some(Ok(answer)) => answer.clone(),
+            Some(Err(_)) => "failed".to_string(),
+            None => "-".to_string(),
+        };
+        let time = |n| part(n).map_or_else(String::new, |p| format!("{:.2?}", p.time));
+        Self {
+            cells: [
+                format!("{day:02}"),
+                answer(1),
+                answer(2),
+                format!("{:.2?}", run.parse_time),
+                time(1),
+                time(2),
+                format!("{:.2?}", run.total_time()),
+                check.to_string(),
+            ],
+        }
+    }
+
+    fn error(day: u8, msg: String) -> Self {
+        Self {
+            cells: std::array::from_fn(|i| match i {
+                0 => format!("{day:02}"),
+                1 => msg.clone(),
+                _ => String::new(),
+            }),
+        }
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    const HEADER: [&str; COLUMNS] = [
+        "Day",
+        "Part 1",
+        "Part 2",
+        "Parse",
+        "Part 1 time",
+        "Part 2 time",
+        "Total",
+        "Check",
+    ];
+
+    let mut widths = HEADER.map(str::len);
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(&row.cells) {
+            *w = (*w).max(cell.len());
+        }
+    }
+
+    let line = |cells: [&str; COLUMNS]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        println!("{}", padded.join(" | ").trim_end());
+    };
+
+    line(HEADER);
+    let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    println!("{}", separator.join("-+-"));
+    for row in rows {
+        line(row.cells.each_ref().map(String::as_str));
+    }
+}
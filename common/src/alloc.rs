@@ -0,0 +1,140 @@
+//! Heap usage tracking, enabled by the `alloc-stats` feature.
+//!
+//! With the feature, a counting allocator wrapping [`System`] is installed as
+//! the global allocator of every binary linking `common`, and [`measure`]
+//! reports the allocations of a closure. Without it, [`measure`] only runs the
+//! closure.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator counting allocations and live heap bytes.
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+impl Counting {
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: all allocation is delegated to `System`, only counters are updated.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded with the caller's guarantees.
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded with the caller's guarantees.
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: forwarded with the caller's guarantees.
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a measured closure.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included.
+    pub allocations: usize,
+    /// Highest number of live heap bytes above the level at the start.
+    pub peak_bytes: usize,
+}
+
+impl AllocStats {
+    /// The larger count and peak of `self` and `other`, e.g. the worst of several runs.
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            allocations: self.allocations.max(other.allocations),
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+        }
+    }
+}
+
+/// Whether allocations are tracked, i.e. the `alloc-stats` feature is enabled.
+pub const fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Runs `f` and returns its value with its heap usage, if [`enabled`].
+///
+/// Counters are process wide, so allocations of other threads running at the
+/// same time are included. Measurements may be nested.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = CURRENT.load(Ordering::Relaxed);
+    let outer_peak = PEAK.swap(start, Ordering::Relaxed);
+
+    let value = f();
+
+    let peak = PEAK.fetch_max(outer_peak, Ordering::Relaxed);
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        peak_bytes: peak.saturating_sub(start),
+    };
+    (value, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| {
+            let a = black_box(vec![0u8; 1000]);
+            let ((), inner) = measure(|| drop(black_box(vec![0u8; 100])));
+            assert_eq!(inner.is_some(), enabled());
+            a.len()
+        });
+        assert_eq!(len, 1000);
+        match stats {
+            Some(stats) => {
+                assert!(stats.allocations >= 2);
+                assert!(stats.peak_bytes >= 1100);
+            }
+            None => assert!(!enabled()),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Name of the answers file, next to the puzzle inputs.
+pub const FILE_NAME: &str = "answers.toml";
+
+/// Known answers of the puzzle inputs, keyed by day and part.
+///
+/// The file is a small subset of TOML: one `[dayXX]` table per day with
+/// `partN` keys holding integers or strings, and `#` comments.
+///
+/// ```toml
+/// [day01]
+/// part1 = 1150
+/// part2 = "6738"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+/// Outcome of comparing an answer with the known one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check<'a> {
+    /// No answer is recorded for this part.
+    Unknown,
+    Ok,
+    Mismatch {
+        expected: &'a str,
+    },
+}
+
+impl Answers {
+    /// Parses the contents of an answers file.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+        let mut day = None;
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                let parsed = table
+                    .strip_suffix(']')
+                    .and_then(|t| t.trim().strip_prefix("day"))
+                    .and_then(|d| d.parse().ok());
+                day = Some(parsed.ok_or_else(|| {
+                    format!("line {line_no}: expected a `[dayXX]` table, found `{line}`")
+                })?);
+                continue;
+            }
+
+            let Some(day) = day else {
+                return Err(format!(
+                    "line {line_no}: answer outside of a `[dayXX]` table"
+                ));
+            };
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_no}: expected `partN = answer`"))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(|| {
+                    format!("line {line_no}: expected `partN`, found `{}`", key.trim())
+                })?;
+            let value = parse_value(value.trim())
+                .ok_or_else(|| format!("line {line_no}: invalid answer `{}`", value.trim()))?;
+            if answers.insert((day, part), value).is_some() {
+                return Err(format!(
+                    "line {line_no}: duplicate answer for day {day} part {part}"
+                ));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    /// Reads the answers file at `path`. A missing file has no answers.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("cannot read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compares `answer` with the known answer of `part` of `day`.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check<'_> {
+        match self.get(day, part) {
+            None => Check::Unknown,
+            Some(expected) if expected == answer => Check::Ok,
+            Some(expected) => Check::Mismatch { expected },
+        }
+    }
+}
+
+impl fmt::Display for Check<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => Ok(()),
+            Self::Ok => write!(f, "OK"),
+            Self::Mismatch { expected } => write!(f, "MISMATCH (expected {expected})"),
+        }
+    }
+}
+
+/// Removes a `#` comment, unless the `#` is inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// An integer or a `"string"` without escapes.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(s) = value.strip_prefix('"') {
+        let s = s.strip_suffix('"')?;
+        return (!s.contains(['"', '\\'])).then(|| s.to_string());
+    }
+    let digits = value.strip_prefix('-').unwrap_or(value).replace('_', "");
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(value.replace('_', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# Answers of my inputs\n\
+             [day01]\n\
+             part1 = 1_150 # comment\n\
+             part2 = \"ab#c\"\n\
+             \n\
+             [day12]\n\
+             part1 = -3\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("1150"));
+        assert_eq!(answers.get(1, 2), Some("ab#c"));
+        assert_eq!(answers.get(12, 1), Some("-3"));
+        assert_eq!(answers.get(12, 2), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answers::parse("part1 = 1").unwrap_err(),
+            "line 1: answer outside of a `[dayXX]` table"
+        );
+        assert_eq!(
+            Answers::parse("[day01]\npart1 = 1\npart1 = 2").unwrap_err(),
+            "line 3: duplicate answer for day 1 part 1"
+        );
+        assert!(Answers::parse("[dayone]").is_err());
+        assert!(Answers::parse("[day01]\nanswer = 1").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("[day01]\npart1 = \"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[day05]\npart1 = 3").unwrap();
+        assert_eq!(answers.check(5, 1, "3"), Check::Ok);
+        assert_eq!(answers.check(5, 1, "4"), Check::Mismatch { expected: "3" });
+        assert_eq!(answers.check(5, 2, "14"), Check::Unknown);
+        assert_eq!(
+            Check::Mismatch { expected: "3" }.to_string(),
+            "MISMATCH (expected 3)"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Grid of bits of any width, each row stored in `width.div_ceil(64)` words.
+///
+/// Column `x` is bit `x % 64` of word `x / 64` of its row, so a row mask
+/// shifted left by `x` covers columns from `x` on. Bits past the width are
+/// always clear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    /// Bits of the last word of a row that lie within the width.
+    last_word_mask: u64,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Grid of `width * height` clear bits.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        Self {
+            width,
+            height,
+            words_per_row,
+            last_word_mask: match width % WORD_BITS {
+                0 => !0,
+                n => (1 << n) - 1,
+            },
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The words of row `y`, lowest columns first.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Index in `words` of the word holding column `x` of row `y`.
+    const fn word_index(&self, x: usize, y: usize) -> usize {
+        y * self.words_per_row + x / WORD_BITS
+    }
+
+    /// Bits of word `i` of a row that lie within the width.
+    const fn valid_bits(&self, i: usize) -> u64 {
+        if i + 1 == self.words_per_row {
+            self.last_word_mask
+        } else {
+            !0
+        }
+    }
+
+    /// # Panics
+    /// If `(x, y)` is out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        self.words[self.word_index(x, y)] >> (x % WORD_BITS) & 1 == 1
+    }
+
+    /// # Panics
+    /// If `(x, y)` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        let i = self.word_index(x, y);
+        let word = &mut self.words[i];
+        if value {
+            *word |= 1 << (x % WORD_BITS);
+        } else {
+            *word &= !(1 << (x % WORD_BITS));
+        }
+    }
+
+    /// `mask` shifted to start at column `x`, as the words at `x / 64` and the one after.
+    const fn shifted(x: usize, mask: u64) -> (u64, u64) {
+        let shift = x % WORD_BITS;
+        let high = if shift == 0 {
+            0
+        } else {
+            mask >> (WORD_BITS - shift)
+        };
+        (mask << shift, high)
+    }
+
+    /// Whether `mask` shifted to start at column `x` of row `y` lies within the
+    /// grid and only covers clear bits.
+    #[inline]
+    pub fn fits(&self, x: usize, y: usize, mask: u64) -> bool {
+        let mask_width = (u64::BITS - mask.leading_zeros()) as usize;
+        if y >= self.height || x + mask_width > self.width {
+            return false;
+        }
+        let (low, high) = Self::shifted(x, mask);
+        let i = self.word_index(x, y);
+        (low == 0 || self.words[i] & low == 0) && (high == 0 || self.words[i + 1] & high == 0)
+    }
+
+    /// Sets the bits of `mask` shifted to start at column `x` of row `y`.
+    ///
+    /// # Panics
+    /// If the mask does not lie within the grid, see [`BitGrid::fits`].
+    #[inline]
+    pub fn insert(&mut self, x: usize, y: usize, mask: u64) {
+        self.update(x, y, mask, |word, bits| *word |= bits);
+    }
+
+    /// Clears the bits of `mask` shifted to start at column `x` of row `y`.
+    ///
+    /// # Panics
+    /// If the mask does not lie within the grid, see [`BitGrid::fits`].
+    #[inline]
+    pub fn remove(&mut self, x: usize, y: usize, mask: u64) {
+        self.update(x, y, mask, |word, bits| *word &= !bits);
+    }
+
+    #[inline]
+    fn update(&mut self, x: usize, y: usize, mask: u64, f: impl Fn(&mut u64, u64)) {
+        let mask_width = (u64::BITS - mask.leading_zeros()) as usize;
+        assert!(
+            y < self.height && x + mask_width <= self.width,
+            "Mask {mask:#b} at ({x}, {y}) out of bounds"
+        );
+        let (low, high) = Self::shifted(x, mask);
+        let i = self.word_index(x, y);
+        if low != 0 {
+            f(&mut self.words[i], low);
+        }
+        if high != 0 {
+            f(&mut self.words[i + 1], high);
+        }
+    }
+
+    pub fn is_row_full(&self, y: usize) -> bool {
+        self.row(y)
+            .iter()
+            .enumerate()
+            .all(|(i, &word)| word == self.valid_bits(i))
+    }
+
+    /// First clear bit as `(x, y)`, scanning rows from `start_y` on.
+    #[inline]
+    pub fn first_empty(&self, start_y: usize) -> Option<(usize, usize)> {
+        for y in start_y..self.height {
+            for (i, &word) in self.row(y).iter().enumerate() {
+                let empty = !word & self.valid_bits(i);
+                if empty != 0 {
+                    return Some((i * WORD_BITS + empty.trailing_zeros() as usize, y));
+                }
+            }
+        }
+        None
+    }
+
+    /// First column at or after `x` of row `y` whose bit is `value`, or the width.
+    fn next_bit(&self, x: usize, y: usize, value: bool) -> usize {
+        let row = self.row(y);
+        let word = |i: usize| (if value { row[i] } else { !row[i] }) & self.valid_bits(i);
+        let mut i = x / WORD_BITS;
+        if i >= self.words_per_row {
+            return self.width;
+        }
+        let mut bits = word(i) & (!0 << (x % WORD_BITS));
+        while bits == 0 {
+            i += 1;
+            if i == self.words_per_row {
+                return self.width;
+            }
+            bits = word(i);
+        }
+        i * WORD_BITS + bits.trailing_zeros() as usize
+    }
+
+    /// First column of the run of clear bits of row `y` that ends at `x`.
+    fn run_start(&self, x: usize, y: usize) -> usize {
+        let row = self.row(y);
+        let mut i = x / WORD_BITS;
+        let mut bits = row[i] & ((1 << (x % WORD_BITS)) - 1);
+        while bits == 0 {
+            if i == 0 {
+                return 0;
+            }
+            i -= 1;
+            bits = row[i];
+        }
+        i * WORD_BITS + WORD_BITS - bits.leading_zeros() as usize
+    }
+
+    /// Sets the bits of columns `start..end` of row `y`.
+    fn set_span(&mut self, start: usize, end: usize, y: usize) {
+        let mut x = start;
+        while x < end {
+            let shift = x % WORD_BITS;
+            let len = (WORD_BITS - shift).min(end - x);
+            let mask = if len == WORD_BITS { !0 } else { (1 << len) - 1 };
+            let i = self.word_index(x, y);
+            self.words[i] |= mask << shift;
+            x += len;
+        }
+    }
+
+    /// Sets every clear bit connected to `(x, y)` through orthogonal clear
+    /// neighbours, returning how many bits were set.
+    ///
+    /// Fills whole runs of clear bits within a row at once, so it is much
+    /// faster than a flood fill one cell at a time.
+    ///
+    /// # Panics
+    /// If `(x, y)` is out of bounds.
+    pub fn fill_region(&mut self, x: usize, y: usize) -> usize {
+        let mut filled = 0;
+        let mut seeds = vec![(x, y)];
+        while let Some((x, y)) = seeds.pop() {
+            if self.get(x, y) {
+                continue;
+            }
+            let start = self.run_start(x, y);
+            let end = self.next_bit(x, y, true);
+            self.set_span(start, end, y);
+            filled += end - start;
+            // One seed per clear run of the rows above and below touching the span.
+            for y in [y.wrapping_sub(1), y + 1] {
+                if y >= self.height {
+                    continue;
+                }
+                let mut x = self.next_bit(start, y, false);
+                while x < end {
+                    seeds.push((x, y));
+                    x = self.next_bit(self.next_bit(x, y, true), y, false);
+                }
+            }
+        }
+        filled
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Copies the bits of a grid of the same size without reallocating.
+    ///
+    /// # Panics
+    /// If the sizes differ.
+    pub fn copy_from(&mut self, other: &Self) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "BitGrid size mismatch"
+        );
+        self.words.copy_from_slice(&other.words);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_across_words() {
+        let mut grid = BitGrid::new(130, 2);
+        assert_eq!(grid.row(0).len(), 3);
+        assert!(grid.fits(62, 1, 0b111));
+        grid.insert(62, 1, 0b111);
+        assert!(grid.get(63, 1) && grid.get(64, 1) && !grid.get(65, 1));
+        assert!(!grid.fits(60, 1, 0b100));
+        assert!(grid.fits(60, 1, 0b11));
+        assert!(grid.fits(128, 0, 0b11));
+        assert!(!grid.fits(128, 0, 0b111));
+        assert!(!grid.fits(0, 2, 1));
+        assert_eq!(grid.count_ones(), 3);
+        grid.remove(62, 1, 0b101);
+        assert_eq!(grid.row(1), [1 << 63, 0, 0]);
+    }
+
+    #[test]
+    fn test_fill_region() {
+        // Two regions split by a wall across the word boundary, one of them
+        // wrapping around a block.
+        let mut grid = BitGrid::new(100, 3);
+        for y in 0..3 {
+            grid.set(70, y, true);
+        }
+        grid.insert(10, 1, 0b1111);
+        assert_eq!(grid.fill_region(0, 0), 70 * 3 - 4);
+        assert_eq!(grid.first_empty(0), Some((71, 0)));
+        assert_eq!(grid.fill_region(99, 2), 29 * 3);
+        assert_eq!(grid.fill_region(5, 1), 0);
+        assert_eq!(grid.first_empty(0), None);
+        assert_eq!(grid.count_ones(), 300);
+    }
+
+    #[test]
+    fn test_full_rows() {
+        let mut grid = BitGrid::new(70, 2);
+        for x in 0..70 {
+            grid.set(x, 0, true);
+        }
+        assert!(grid.is_row_full(0));
+        assert_eq!(grid.first_empty(0), Some((0, 1)));
+        grid.insert(0, 1, !0);
+        grid.insert(64, 1, 0b11111);
+        assert_eq!(grid.first_empty(0), Some((69, 1)));
+        grid.set(69, 1, true);
+        assert_eq!(grid.first_empty(0), None);
+
+        let mut copy = BitGrid::new(70, 2);
+        copy.copy_from(&grid);
+        assert_eq!(copy, grid);
+        assert_eq!(BitGrid::new(64, 1).first_empty(0), Some((0, 0)));
+        assert!(BitGrid::new(64, 1).fits(64, 0, 0));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::parse::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Error of a solution, usually invalid puzzle input.
+///
+/// Carries where the problem was found, as far as it is known, so binaries can
+/// print `day 5, line 3, column 4: invalid `end` `x4`` instead of panicking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub day: Option<u8>,
+    /// 1-based line of the input.
+    pub line: Option<usize>,
+    /// 1-based column (in bytes) within the line.
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl AocError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line: None,
+            column: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub const fn with_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    pub const fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub const fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        Self {
+            day: None,
+            line: e.line,
+            column: Some(e.column),
+            reason: e.message,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut location = Vec::new();
+        if let Some(day) = self.day {
+            location.push(format!("day {day}"));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {line}"));
+        }
+        if let Some(column) = self.column {
+            location.push(format!("column {column}"));
+        }
+        if location.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.reason)
+        }
+    }
+}
+
+impl Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AocError::new("empty input").to_string(), "empty input");
+        let err = AocError::from(ParseError::new(4, "invalid `end` `x4`").at_line(3)).with_day(5);
+        assert_eq!(
+            err.to_string(),
+            "day 5, line 3, column 4: invalid `end` `x4`"
+        );
+        assert_eq!(
+            AocError::new("no operator").at_line(5).to_string(),
+            "line 5: no operator"
+        );
+    }
+}
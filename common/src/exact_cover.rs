@@ -0,0 +1,390 @@
+use std::ops::ControlFlow;
+
+/// Header of the list of primary columns left to cover.
+const ROOT: usize = 0;
+
+/// Exact cover problem solved with Knuth's Algorithm X and dancing links.
+///
+/// Columns `0..primary` must be covered by exactly one chosen row, or by as
+/// many as set with [`ExactCover::set_multiplicity`], and columns
+/// `primary..primary + secondary` by at most one, e.g. cells of a packing
+/// puzzle that may stay empty. Rows are numbered in the order they are added.
+///
+/// Rows covering a column with a multiplicity are chosen in the order they
+/// were added, so identical pieces of a puzzle do not multiply the solutions.
+///
+/// ```
+/// use common::exact_cover::ExactCover;
+///
+/// let mut problem = ExactCover::new(3, 0);
+/// problem.add_row(&[0, 1]);
+/// problem.add_row(&[2]);
+/// problem.add_row(&[1, 2]);
+/// problem.add_row(&[0]);
+/// assert_eq!(problem.count_solutions(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    primary: usize,
+    rows: usize,
+    // Node 0 is the root, nodes `1..=columns` the column headers, then the
+    // nodes of the rows. Secondary headers are not linked into the root list.
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Header node of the column of each node.
+    column: Vec<usize>,
+    /// Row of each node, unused for headers.
+    row: Vec<usize>,
+    /// Number of rows left in each column, indexed by header node.
+    size: Vec<usize>,
+    /// Number of rows each column still needs, 0 once it is covered.
+    need: Vec<usize>,
+}
+
+impl ExactCover {
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        let headers = 1 + primary + secondary;
+        let mut left: Vec<usize> = (0..headers).collect();
+        let mut right: Vec<usize> = (0..headers).collect();
+        // Link the root and the primary headers into a ring.
+        for h in 0..=primary {
+            right[h] = if h == primary { ROOT } else { h + 1 };
+            left[h] = if h == ROOT { primary } else { h - 1 };
+        }
+        Self {
+            primary,
+            rows: 0,
+            left,
+            right,
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            need: vec![1; headers],
+        }
+    }
+
+    /// Requires primary column `c` to be covered by exactly `count` rows.
+    ///
+    /// # Panics
+    /// If `c` is not a primary column or `count` is 0.
+    pub fn set_multiplicity(&mut self, c: usize, count: usize) {
+        assert!(c < self.primary, "Column {c} is not primary");
+        assert!(count > 0, "Column {c} must be covered at least once");
+        self.need[c + 1] = count;
+    }
+
+    /// Number of primary columns.
+    pub const fn primary(&self) -> usize {
+        self.primary
+    }
+
+    /// Number of primary and secondary columns.
+    pub const fn columns(&self) -> usize {
+        self.size.len() - 1
+    }
+
+    /// Number of rows added so far.
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Adds a row covering `columns`, returning its number.
+    ///
+    /// # Panics
+    /// If a column is out of range or listed twice.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.rows;
+        self.rows += 1;
+        let first = self.left.len();
+        for (i, &c) in columns.iter().enumerate() {
+            assert!(c < self.columns(), "Column {c} out of range");
+            assert!(!columns[..i].contains(&c), "Column {c} listed twice");
+            let header = c + 1;
+            let node = self.left.len();
+            let last = self.up[header];
+            self.left.push(if i == 0 { node } else { node - 1 });
+            self.right.push(first);
+            self.up.push(last);
+            self.down.push(header);
+            self.column.push(header);
+            self.row.push(row);
+            self.down[last] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+            if i > 0 {
+                self.right[node - 1] = node;
+                self.left[first] = node;
+            }
+        }
+        row
+    }
+
+    /// Removes column `c` and every row covering it from the matrix.
+    fn cover(&mut self, c: usize) {
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = r;
+        self.left[r] = l;
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Undoes [`ExactCover::cover`], in the reverse order.
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                self.size[self.column[j]] += 1;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = c;
+        self.left[r] = c;
+    }
+
+    /// Unlinks the nodes of row `r` from their columns.
+    fn hide(&mut self, r: usize) {
+        let mut j = r;
+        loop {
+            let (u, d) = (self.up[j], self.down[j]);
+            self.down[u] = d;
+            self.up[d] = u;
+            self.size[self.column[j]] -= 1;
+            j = self.right[j];
+            if j == r {
+                break;
+            }
+        }
+    }
+
+    /// Undoes [`ExactCover::hide`].
+    fn unhide(&mut self, r: usize) {
+        let mut j = self.left[r];
+        loop {
+            let (u, d) = (self.up[j], self.down[j]);
+            self.down[u] = j;
+            self.up[d] = j;
+            self.size[self.column[j]] += 1;
+            if j == r {
+                break;
+            }
+            j = self.left[j];
+        }
+    }
+
+    /// Accounts for hidden row `r` being chosen, covering the columns it completes.
+    fn choose(&mut self, r: usize) {
+        let mut j = r;
+        loop {
+            let c = self.column[j];
+            self.need[c] -= 1;
+            if self.need[c] == 0 {
+                self.cover(c);
+            }
+            j = self.right[j];
+            if j == r {
+                break;
+            }
+        }
+    }
+
+    /// Undoes [`ExactCover::choose`].
+    fn unchoose(&mut self, r: usize) {
+        let mut j = self.left[r];
+        loop {
+            let c = self.column[j];
+            if self.need[c] == 0 {
+                self.uncover(c);
+            }
+            self.need[c] += 1;
+            if j == r {
+                break;
+            }
+            j = self.left[j];
+        }
+    }
+
+    /// Calls `visit` with the rows of every solution until it breaks, and
+    /// returns its break value.
+    ///
+    /// Rows are listed in the order they were chosen. The matrix is restored
+    /// afterwards, so the problem can be solved again.
+    pub fn solve<B>(&mut self, mut visit: impl FnMut(&[usize]) -> ControlFlow<B>) -> Option<B> {
+        match self.search(&mut Vec::new(), &mut Vec::new(), &mut visit) {
+            ControlFlow::Break(value) => Some(value),
+            ControlFlow::Continue(()) => None,
+        }
+    }
+
+    fn search<B>(
+        &mut self,
+        solution: &mut Vec<usize>,
+        hidden: &mut Vec<usize>,
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        if self.right[ROOT] == ROOT {
+            return visit(solution);
+        }
+        // Branch on the column with the fewest spare rows, giving up if one
+        // has fewer rows left than it needs.
+        let mut c = ROOT;
+        let mut j = self.right[ROOT];
+        while j != ROOT {
+            if self.size[j] < self.need[j] {
+                return ControlFlow::Continue(());
+            }
+            if c == ROOT || self.size[j] - self.need[j] < self.size[c] - self.need[c] {
+                c = j;
+            }
+            j = self.right[j];
+        }
+
+        // Rows tried already stay hidden until all are, so the other rows
+        // chosen for `c` further down come after this one.
+        let tried = hidden.len();
+        let mut r = self.down[c];
+        let mut flow = ControlFlow::Continue(());
+        while r != c && flow.is_continue() {
+            self.hide(r);
+            hidden.push(r);
+            self.choose(r);
+            solution.push(self.row[r]);
+            flow = self.search(solution, hidden, visit);
+            solution.pop();
+            self.unchoose(r);
+            r = self.down[r];
+        }
+        while hidden.len() > tried {
+            let r = hidden.pop().unwrap();
+            self.unhide(r);
+        }
+        flow
+    }
+
+    /// Rows of some solution, if there is one.
+    pub fn first_solution(&mut self) -> Option<Vec<usize>> {
+        self.solve(|rows| ControlFlow::Break(rows.to_vec()))
+    }
+
+    pub fn all_solutions(&mut self) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        self.solve::<()>(|rows| {
+            solutions.push(rows.to_vec());
+            ControlFlow::Continue(())
+        });
+        solutions
+    }
+
+    pub fn count_solutions(&mut self) -> usize {
+        let mut count = 0;
+        self.solve::<()>(|_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Knuth's example from "Dancing Links", with columns A..G as 0..6.
+    fn knuth() -> ExactCover {
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row);
+        }
+        problem
+    }
+
+    #[test]
+    fn test_knuth_example() {
+        let mut problem = knuth();
+        let mut solution = problem.first_solution().unwrap();
+        solution.sort_unstable();
+        assert_eq!(solution, [0, 3, 4]);
+        assert_eq!(problem.count_solutions(), 1);
+        assert_eq!(problem.all_solutions().len(), 1);
+
+        problem.add_row(&[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(problem.count_solutions(), 2);
+        assert_eq!(problem.rows(), 7);
+    }
+
+    #[test]
+    fn test_secondary_columns() {
+        // Dominoes on a 1x3 strip: two primary pieces, three secondary cells.
+        let mut problem = ExactCover::new(2, 3);
+        for piece in 0..2 {
+            for x in 0..2 {
+                problem.add_row(&[piece, 2 + x, 3 + x]);
+            }
+        }
+        assert_eq!(problem.count_solutions(), 0);
+
+        // A single domino may leave either end empty.
+        let mut problem = ExactCover::new(1, 3);
+        problem.add_row(&[0, 1, 2]);
+        problem.add_row(&[0, 2, 3]);
+        let mut solutions = problem.all_solutions();
+        solutions.sort();
+        assert_eq!(solutions, [[0], [1]]);
+    }
+
+    #[test]
+    fn test_multiplicity() {
+        // Two identical dominoes on a 1x4 strip fit one way only.
+        let mut problem = ExactCover::new(1, 4);
+        problem.set_multiplicity(0, 2);
+        for x in 0..3 {
+            problem.add_row(&[0, 1 + x, 2 + x]);
+        }
+        assert_eq!(problem.all_solutions(), [[0, 2]]);
+
+        // Two of three cells, each pair once.
+        let mut problem = ExactCover::new(1, 3);
+        problem.set_multiplicity(0, 2);
+        for x in 0..3 {
+            problem.add_row(&[0, 1 + x]);
+        }
+        assert_eq!(problem.count_solutions(), 3);
+        problem.set_multiplicity(0, 3);
+        assert_eq!(problem.count_solutions(), 1);
+    }
+
+    #[test]
+    fn test_no_columns() {
+        // The empty set of rows covers nothing.
+        let mut problem = ExactCover::new(0, 2);
+        problem.add_row(&[0]);
+        assert_eq!(problem.all_solutions(), [Vec::<usize>::new()]);
+        assert_eq!(ExactCover::new(1, 0).first_solution(), None);
+    }
+}
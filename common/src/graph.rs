@@ -0,0 +1,423 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Assigns dense ids `0, 1, ...` to names in the order they are first seen.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `name`, assigning the next one if it is new.
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = u32::try_from(self.names.len()).expect("Too many names to intern");
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> Option<&str> {
+        self.names.get(id as usize).map(String::as_str)
+    }
+
+    pub const fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Adjacency lists of the nodes `0..n` in compressed sparse row form: the list
+/// of `node` is `targets[offsets[node]..offsets[node + 1]]`.
+#[derive(Debug, Clone)]
+struct Csr {
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+}
+
+impl Csr {
+    /// Lists the `(from, to)` edges by `from`, keeping their order.
+    fn build(nodes: usize, edges: &[(u32, u32)]) -> Self {
+        // Counting sort of the edges by source.
+        let mut offsets = vec![0; nodes + 1];
+        for &(from, to) in edges {
+            assert!(
+                (from as usize) < nodes && (to as usize) < nodes,
+                "Edge ({from}, {to}) out of range"
+            );
+            offsets[from as usize + 1] += 1;
+        }
+        for i in 0..nodes {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        for &(from, to) in edges {
+            targets[next[from as usize]] = to;
+            next[from as usize] += 1;
+        }
+        Self { offsets, targets }
+    }
+
+    fn row(&self, node: u32) -> &[u32] {
+        let node = node as usize;
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+}
+
+/// Directed graph over the nodes `0..n`, stored in compressed sparse row form.
+///
+/// The edges leaving each node are kept in the order they were given, and the
+/// edges entering it in a second CSR, so searches can run in both directions.
+/// Graphs built from named edges keep the names, see [`Graph::id`] and
+/// [`Graph::name`].
+#[derive(Debug, Clone)]
+pub struct Graph {
+    forward: Csr,
+    backward: Csr,
+    names: Interner,
+}
+
+impl Graph {
+    /// Graph with `nodes` nodes and the given `(from, to)` edges.
+    ///
+    /// # Panics
+    /// If an edge refers to a node out of range.
+    pub fn from_edges(nodes: usize, edges: &[(u32, u32)]) -> Self {
+        Self::build(nodes, edges, Interner::new())
+    }
+
+    /// Graph of named nodes from the targets of each source, e.g. the lines
+    /// `aaa: you hhh` of a puzzle input.
+    ///
+    /// Nodes are numbered in the order their names first appear.
+    pub fn from_adjacency<'a, T>(lists: impl IntoIterator<Item = (&'a str, T)>) -> Self
+    where
+        T: IntoIterator<Item = &'a str>,
+    {
+        let mut names = Interner::new();
+        let mut edges = Vec::new();
+        for (source, targets) in lists {
+            let from = names.intern(source);
+            for target in targets {
+                edges.push((from, names.intern(target)));
+            }
+        }
+        Self::build(names.len(), &edges, names)
+    }
+
+    fn build(nodes: usize, edges: &[(u32, u32)], names: Interner) -> Self {
+        let forward = Csr::build(nodes, edges);
+        let reversed: Vec<_> = edges.iter().map(|&(from, to)| (to, from)).collect();
+        let backward = Csr::build(nodes, &reversed);
+        Self {
+            forward,
+            backward,
+            names,
+        }
+    }
+
+    /// Number of nodes.
+    pub const fn len(&self) -> usize {
+        self.forward.offsets.len() - 1
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub const fn edge_count(&self) -> usize {
+        self.forward.targets.len()
+    }
+
+    /// Node named `name`, if the graph was built from names.
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, node: u32) -> Option<&str> {
+        self.names.name(node)
+    }
+
+    /// Targets of the edges leaving `node`.
+    pub fn neighbors(&self, node: u32) -> &[u32] {
+        self.forward.row(node)
+    }
+
+    /// Sources of the edges entering `node`.
+    pub fn predecessors(&self, node: u32) -> &[u32] {
+        self.backward.row(node)
+    }
+
+    /// All edges as `(from, to)`, ordered by source.
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (0..self.len() as u32)
+            .flat_map(move |from| self.neighbors(from).iter().map(move |&to| (from, to)))
+    }
+
+    /// The graph with every edge reversed, keeping the names.
+    pub fn reversed(&self) -> Self {
+        Self {
+            forward: self.backward.clone(),
+            backward: self.forward.clone(),
+            names: self.names.clone(),
+        }
+    }
+
+    /// Nodes reachable from `from`, `from` included, as a flag per node.
+    pub fn reachable(&self, from: u32) -> Vec<bool> {
+        Self::search(&self.forward, from)
+    }
+
+    /// Nodes from which `to` is reachable, `to` included, as a flag per node.
+    pub fn reaching(&self, to: u32) -> Vec<bool> {
+        Self::search(&self.backward, to)
+    }
+
+    /// Nodes reachable from `start` along the lists of `csr`.
+    fn search(csr: &Csr, start: u32) -> Vec<bool> {
+        let mut seen = vec![false; csr.offsets.len() - 1];
+        seen[start as usize] = true;
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in csr.row(node) {
+                if !seen[next as usize] {
+                    seen[next as usize] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Nodes ordered so that every edge points forwards, or `None` if the
+    /// graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<u32>> {
+        let mut in_degree = vec![0usize; self.len()];
+        for &to in &self.forward.targets {
+            in_degree[to as usize] += 1;
+        }
+        let mut queue: VecDeque<u32> = (0..self.len() as u32)
+            .filter(|&node| in_degree[node as usize] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in self.neighbors(node) {
+                in_degree[next as usize] -= 1;
+                if in_degree[next as usize] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Strongly connected components, found with Tarjan's algorithm.
+    ///
+    /// Components come in reverse topological order: every edge between two
+    /// components points to an earlier one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<u32>> {
+        const UNVISITED: usize = usize::MAX;
+        let mut index = vec![UNVISITED; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+        // Depth-first search without recursion: each frame is a node and the
+        // number of its edges followed so far.
+        let mut frames: Vec<(u32, usize)> = Vec::new();
+
+        for root in 0..self.len() as u32 {
+            if index[root as usize] != UNVISITED {
+                continue;
+            }
+            frames.push((root, 0));
+            while let Some(&mut (node, ref mut followed)) = frames.last_mut() {
+                let n = node as usize;
+                if *followed == 0 && index[n] == UNVISITED {
+                    index[n] = next_index;
+                    low[n] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[n] = true;
+                }
+                if let Some(&next) = self.neighbors(node).get(*followed) {
+                    *followed += 1;
+                    if index[next as usize] == UNVISITED {
+                        frames.push((next, 0));
+                    } else if on_stack[next as usize] {
+                        low[n] = low[n].min(index[next as usize]);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some(&(parent, _)) = frames.last() {
+                    low[parent as usize] = low[parent as usize].min(low[n]);
+                }
+                if low[n] == index[n] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member as usize] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Number of distinct paths from `from` to `to`, or `None` if there are
+    /// infinitely many because a cycle lies on one of them.
+    ///
+    /// A node has a single (empty) path to itself.
+    pub fn count_paths(&self, from: u32, to: u32) -> Option<u64> {
+        let forward = self.reachable(from);
+        if !forward[to as usize] {
+            return Some(0);
+        }
+        let backward = self.reaching(to);
+        let on_path: Vec<bool> = forward
+            .iter()
+            .zip(&backward)
+            .map(|(&f, &b)| f && b)
+            .collect();
+
+        // Kahn's algorithm on the nodes between `from` and `to`, which all
+        // get processed unless some of them form a cycle.
+        let mut in_degree = vec![0usize; self.len()];
+        for (from, to) in self.edges() {
+            if on_path[from as usize] && on_path[to as usize] {
+                in_degree[to as usize] += 1;
+            }
+        }
+        if in_degree[from as usize] > 0 {
+            return None;
+        }
+        let mut paths = vec![0u64; self.len()];
+        paths[from as usize] = 1;
+        let mut queue = VecDeque::from([from]);
+        let mut processed = 0;
+        while let Some(node) = queue.pop_front() {
+            processed += 1;
+            for &next in self.neighbors(node) {
+                let n = next as usize;
+                if on_path[n] {
+                    paths[n] += paths[node as usize];
+                    in_degree[n] -= 1;
+                    if in_degree[n] == 0 {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        let between = on_path.iter().filter(|&&b| b).count();
+        (processed == between).then_some(paths[to as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph {
+        // a -> b -> d, a -> c -> d, d -> e, plus an unconnected f.
+        Graph::from_adjacency([
+            ("a", vec!["b", "c"]),
+            ("b", vec!["d"]),
+            ("c", vec!["d"]),
+            ("d", vec!["e"]),
+            ("f", vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_names_and_csr() {
+        let graph = example();
+        assert_eq!((graph.len(), graph.edge_count()), (6, 5));
+        let a = graph.id("a").unwrap();
+        let d = graph.id("d").unwrap();
+        assert_eq!(graph.name(d), Some("d"));
+        assert_eq!(graph.id("x"), None);
+        let names: Vec<_> = graph
+            .neighbors(a)
+            .iter()
+            .map(|&n| graph.name(n).unwrap())
+            .collect();
+        assert_eq!(names, ["b", "c"]);
+        assert_eq!(graph.predecessors(d).len(), 2);
+        assert_eq!(graph.reversed().neighbors(d), graph.predecessors(d));
+
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("svr"), 0);
+        assert_eq!(interner.intern("a-long-name"), 1);
+        assert_eq!(interner.intern("svr"), 0);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_order_and_reachability() {
+        let graph = example();
+        let order = graph.topological_order().unwrap();
+        let position = |n: u32| order.iter().position(|&m| m == n).unwrap();
+        assert!(
+            graph
+                .edges()
+                .all(|(from, to)| position(from) < position(to))
+        );
+
+        let reachable = graph.reachable(graph.id("b").unwrap());
+        assert_eq!(reachable.iter().filter(|&&r| r).count(), 3);
+        let reaching = graph.reaching(graph.id("d").unwrap());
+        assert_eq!(reaching.iter().filter(|&&r| r).count(), 4);
+
+        let cyclic = Graph::from_edges(3, &[(0, 1), (1, 2), (2, 1)]);
+        assert_eq!(cyclic.topological_order(), None);
+    }
+
+    #[test]
+    fn test_components() {
+        // 0 <-> 1 -> 2 <-> 3 -> 4
+        let graph = Graph::from_edges(5, &[(0, 1), (1, 0), (1, 2), (2, 3), (3, 2), (3, 4)]);
+        let mut components = graph.strongly_connected_components();
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        assert_eq!(components, [vec![4], vec![2, 3], vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = example();
+        let id = |name| graph.id(name).unwrap();
+        assert_eq!(graph.count_paths(id("a"), id("e")), Some(2));
+        assert_eq!(graph.count_paths(id("a"), id("a")), Some(1));
+        assert_eq!(graph.count_paths(id("e"), id("a")), Some(0));
+        assert_eq!(graph.count_paths(id("f"), id("e")), Some(0));
+
+        // The cycle 1 <-> 2 is between 0 and 3, but not between 0 and 4.
+        let graph = Graph::from_edges(5, &[(0, 1), (1, 2), (2, 1), (2, 3), (0, 4)]);
+        assert_eq!(graph.count_paths(0, 3), None);
+        assert_eq!(graph.count_paths(0, 4), Some(1));
+    }
+}
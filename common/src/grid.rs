@@ -0,0 +1,301 @@
+use crate::parse::ParseError;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4 orthogonal neighbours, as `(dx, dy)`.
+const DIRS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8 neighbours including diagonals, as `(dx, dy)`.
+const DIRS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Dense 2D grid stored row by row, indexed by `(x, y)` with `x` the column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width * height` cells row by row.
+    ///
+    /// # Panics
+    /// If the number of cells does not match the dimensions.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Grid size mismatch");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, mapping characters with `f`.
+    ///
+    /// Empty lines are skipped. Characters for which `f` returns `None` and
+    /// rows of different lengths are reported with their line and column.
+    pub fn parse(input: &str, f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Self::from_lines(input.lines(), f)
+    }
+
+    /// Like [`Grid::parse`] for lines that were already split, e.g. a block of a larger input.
+    pub fn from_lines<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+            let start = cells.len();
+            for (col, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::new(col + 1, format!("unexpected character `{c}`")).at_line(i + 1)
+                })?;
+                cells.push(cell);
+            }
+            let row_width = cells.len() - start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::new(
+                        1,
+                        format!("row has {row_width} cells, expected {width}"),
+                    )
+                    .at_line(i + 1));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Index of `(x, y)` in [`Grid::cells`].
+    pub const fn index_of(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Position of the cell at `index` in [`Grid::cells`].
+    pub const fn position(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.in_bounds(x, y)
+            .then(|| &self.cells[self.index_of(x, y)])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.in_bounds(x, y) {
+            let index = self.index_of(x, y);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the cell at `(x, y)`, returning the previous value or `None` if out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        self.get_mut(x, y)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` rejects 0, an empty grid has no rows anyway.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position(i), cell))
+    }
+
+    /// The in-bounds orthogonal neighbours of `(x, y)`.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &DIRS4)
+    }
+
+    /// The in-bounds neighbours of `(x, y)`, diagonals included.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &DIRS8)
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        dirs: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        dirs.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.in_bounds(x, y).then_some((x, y))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Renders the grid as text, one line per row, mapping cells with `f`.
+    pub fn to_text(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(&mut f));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_vec(width, height, vec![value; width * height])
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        &self.cells[self.index_of(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        let index = self.index_of(x, y);
+        &mut self.cells[index]
+    }
+}
+
+/// Writes the cells of each row next to each other, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+        Grid::parse(input, |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = parse("#..\r\n.#.\n\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_text(|&c| if c { '#' } else { '.' }), "#..\n.#.\n");
+        assert_eq!(grid.map(|&c| u8::from(c)).to_string(), "100\n010\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("#..\n.x.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: unexpected character `x`"
+        );
+        let err = parse("#..\n.#").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: row has 2 cells, expected 3"
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert_eq!(grid.set(2, 1, 5), Some(0));
+        assert_eq!(grid.set(3, 1, 5), None);
+        grid[(0, 1)] = 4;
+        assert_eq!(grid.row(1), [4, 0, 5]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [0, 5]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.position(grid.index_of(2, 1)), (2, 1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+    }
+}
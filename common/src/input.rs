@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Environment variable naming an input file, used when no path argument is given.
+pub const ENV_VAR: &str = "AOC_INPUT";
+
+/// Cleans up editor artefacts so every day sees the same text for the same puzzle.
+///
+/// Removes a UTF-8 byte order mark, turns CRLF line endings into LF, strips
+/// trailing whitespace from every line and ends the input with exactly one
+/// newline. Leading and separating blank lines are kept. Borrows `input` if it
+/// is already normalised.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let text = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut normalized = String::with_capacity(text.len() + 1);
+    for line in text.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    if normalized == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Reads the input at `path`, treating `-` as standard input, and [`normalize`]s it.
+pub fn read(path: &Path) -> io::Result<String> {
+    let text = if path == Path::new("-") {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(path)?
+    };
+    let normalized = match normalize(&text) {
+        Cow::Owned(normalized) => Some(normalized),
+        Cow::Borrowed(_) => None,
+    };
+    Ok(normalized.unwrap_or(text))
+}
+
+/// Input path requested at runtime: the first command line argument, then [`ENV_VAR`].
+pub fn path_from_env() -> Option<PathBuf> {
+    env::args_os()
+        .nth(1)
+        .or_else(|| env::var_os(ENV_VAR))
+        .map(PathBuf::from)
+}
+
+/// Input for the binary of `day`.
+///
+/// Uses the runtime path from [`path_from_env`] when present and falls back to the
+/// input embedded at compile time by `aoc_input!`, [`normalize`]d either way.
+/// Exits the process with a diagnostic if the requested file cannot be read, or
+/// if no path is given and nothing was embedded (a build with the
+/// `macros/allow-missing-input` feature).
+pub fn load(day: u8, embedded: &'static str) -> Cow<'static, str> {
+    let Some(path) = path_from_env() else {
+        if embedded.is_empty() {
+            eprintln!("No input embedded for day {day:02}; pass a path, `-`, or set {ENV_VAR}");
+            process::exit(1);
+        }
+        return normalize(embedded);
+    };
+    match read(&path) {
+        Ok(input) => Cow::Owned(input),
+        Err(e) => {
+            eprintln!("Cannot read input {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb\n")));
+        assert_eq!(normalize("\u{feff}a  \r\n\r\nb\t"), "a\n\nb\n");
+        assert_eq!(normalize("\n x\n\n  \n"), "\n x\n");
+        assert_eq!(normalize(" \r\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+}
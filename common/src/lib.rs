@@ -0,0 +1,19 @@
+pub mod alloc;
+pub mod answers;
+pub mod bit_grid;
+pub mod error;
+pub mod exact_cover;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod matrix;
+pub mod parse;
+pub mod point;
+pub mod polygon;
+pub mod range_set;
+pub mod registry;
+mod solution;
+pub mod timing;
+pub mod union_find;
+
+pub use solution::{Solution, SolvePart};
@@ -0,0 +1,425 @@
+use std::ops::{Index, IndexMut};
+
+/// Element types the elimination routines work over.
+///
+/// Implemented for the signed integers, where elimination stays fraction-free,
+/// and for `bool` as GF(2), where addition is XOR and multiplication is AND.
+pub trait Scalar: Copy + PartialEq {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+    /// `self / divisor` if it divides exactly.
+    fn div_exact(self, divisor: Self) -> Option<Self>;
+    /// Greatest common divisor, used to keep rows small.
+    fn gcd(self, other: Self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn add(self, other: Self) -> Self {
+                self + other
+            }
+
+            fn sub(self, other: Self) -> Self {
+                self - other
+            }
+
+            fn mul(self, other: Self) -> Self {
+                self * other
+            }
+
+            fn div_exact(self, divisor: Self) -> Option<Self> {
+                (divisor != 0 && self % divisor == 0).then(|| self / divisor)
+            }
+
+            fn gcd(self, other: Self) -> Self {
+                let (mut a, mut b) = (self.abs(), other.abs());
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            }
+        }
+    )*};
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, isize);
+
+/// GF(2).
+impl Scalar for bool {
+    const ZERO: Self = false;
+    const ONE: Self = true;
+
+    fn add(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self & other
+    }
+
+    fn div_exact(self, divisor: Self) -> Option<Self> {
+        divisor.then_some(self)
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        self | other
+    }
+}
+
+/// Dense row-major matrix, indexed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+/// Row echelon form found by [`Matrix::eliminate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Echelon {
+    /// Number of leading columns that were eliminated, i.e. the variables of a system.
+    pub vars: usize,
+    /// Pivot column of each of the first [`Echelon::rank`] rows.
+    pub pivot_cols: Vec<usize>,
+    /// Variables without a pivot, which can take any value.
+    pub free_cols: Vec<usize>,
+    /// Whether no row reads `0 = b` with `b` non-zero.
+    pub consistent: bool,
+}
+
+impl Echelon {
+    pub const fn rank(&self) -> usize {
+        self.pivot_cols.len()
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Matrix of `rows * cols` elements row by row.
+    ///
+    /// # Panics
+    /// If the number of elements does not match the dimensions.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "Matrix size mismatch");
+        Self { rows, cols, data }
+    }
+
+    /// Matrix with the given rows.
+    ///
+    /// # Panics
+    /// If the rows have different lengths.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut data = Vec::new();
+        let mut cols = None;
+        let mut count = 0;
+        for row in rows {
+            let start = data.len();
+            data.extend(row);
+            let len = data.len() - start;
+            assert_eq!(*cols.get_or_insert(len), len, "Rows have different lengths");
+            count += 1;
+        }
+        Self::from_vec(count, cols.unwrap_or(0), data)
+    }
+
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.data[row * self.cols + col])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        self[(row, col)] = value;
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn swap_rows(&mut self, r1: usize, r2: usize) {
+        if r1 == r2 {
+            return;
+        }
+        let (low, high) = (r1.min(r2), r1.max(r2));
+        let (head, tail) = self.data.split_at_mut(high * self.cols);
+        head[low * self.cols..(low + 1) * self.cols].swap_with_slice(&mut tail[..self.cols]);
+    }
+}
+
+impl<T: Default + Clone> Matrix<T> {
+    /// `rows * cols` matrix of default values, i.e. zeros.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self::from_vec(rows, cols, vec![T::default(); rows * cols])
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// The augmented matrix `[self | rhs]` of the system `self * x = rhs`.
+    ///
+    /// # Panics
+    /// If `rhs` does not have one value per row.
+    pub fn augment(&self, rhs: &[T]) -> Self {
+        assert_eq!(rhs.len(), self.rows, "One right-hand side per row expected");
+        let rows = (0..self.rows).map(|r| self.row(r).iter().chain([&rhs[r]]).cloned());
+        Self::from_rows(rows)
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    /// Multiplies row `row` by `factor`.
+    pub fn scale_row(&mut self, row: usize, factor: T) {
+        for value in self.row_mut(row) {
+            *value = value.mul(factor);
+        }
+    }
+
+    /// Adds `factor` times row `src` to row `dst`.
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, factor: T) {
+        for c in 0..self.cols {
+            let value = self[(src, c)].mul(factor);
+            self[(dst, c)] = self[(dst, c)].add(value);
+        }
+    }
+
+    /// Brings the matrix to row echelon form, pivoting on the first `vars` columns.
+    ///
+    /// Columns after `vars` are transformed along, so for an augmented matrix
+    /// from [`Matrix::augment`] pass the number of variables. Rows are combined
+    /// with multiplications only (`pivot * row - factor * pivot_row`) and divided
+    /// by the GCD of their entries, so integer matrices stay integral.
+    pub fn eliminate(&mut self, vars: usize) -> Echelon {
+        let mut pivot_cols = Vec::new();
+        let mut free_cols = Vec::new();
+        for c in 0..vars.min(self.cols) {
+            let pivot_row = pivot_cols.len();
+            let Some(row) = (pivot_row..self.rows).find(|&r| self[(r, c)] != T::ZERO) else {
+                free_cols.push(c);
+                continue;
+            };
+            self.swap_rows(pivot_row, row);
+            let pivot = self[(pivot_row, c)];
+            for r in pivot_row + 1..self.rows {
+                let factor = self[(r, c)];
+                if factor == T::ZERO {
+                    continue;
+                }
+                let g = pivot.gcd(factor);
+                self.scale_row(r, pivot.div_exact(g).unwrap());
+                self.add_scaled_row(r, pivot_row, T::ZERO.sub(factor.div_exact(g).unwrap()));
+                self.reduce_row(r);
+            }
+            pivot_cols.push(c);
+        }
+        free_cols.extend(self.cols.min(vars)..vars);
+
+        let rank = pivot_cols.len();
+        let consistent = (rank..self.rows).all(|r| self.row(r).iter().all(|&v| v == T::ZERO));
+        Echelon {
+            vars,
+            pivot_cols,
+            free_cols,
+            consistent,
+        }
+    }
+
+    /// Divides row `row` by the GCD of its entries.
+    fn reduce_row(&mut self, row: usize) {
+        let g = self.row(row).iter().fold(T::ZERO, |g, &v| g.gcd(v));
+        if g != T::ZERO && g != T::ONE {
+            for value in self.row_mut(row) {
+                *value = value.div_exact(g).unwrap();
+            }
+        }
+    }
+
+    /// Solves the pivot variables of an eliminated system, bottom row first.
+    ///
+    /// `x` has one value per variable; the values of the free variables are
+    /// read from it and the pivot variables are written. Column `vars` is the
+    /// right-hand side if the matrix has one, zero otherwise. Returns `false`
+    /// if a pivot variable would not be a whole value of `T`, leaving `x`
+    /// partially written.
+    pub fn back_substitute(&self, echelon: &Echelon, x: &mut [T]) -> bool {
+        self.back_substitute_while(echelon, x, |_| true)
+    }
+
+    /// Like [`Matrix::back_substitute`], but also stops with `false` at the
+    /// first pivot variable rejected by `accept`, e.g. a negative one when only
+    /// non-negative solutions count.
+    pub fn back_substitute_while(
+        &self,
+        echelon: &Echelon,
+        x: &mut [T],
+        accept: impl Fn(T) -> bool,
+    ) -> bool {
+        self.substitute(echelon, x, echelon.vars < self.cols, accept)
+    }
+
+    fn substitute(
+        &self,
+        echelon: &Echelon,
+        x: &mut [T],
+        with_rhs: bool,
+        accept: impl Fn(T) -> bool,
+    ) -> bool {
+        for (r, &pc) in echelon.pivot_cols.iter().enumerate().rev() {
+            let row = self.row(r);
+            let mut rhs = if with_rhs { row[echelon.vars] } else { T::ZERO };
+            for k in pc + 1..echelon.vars {
+                rhs = rhs.sub(row[k].mul(x[k]));
+            }
+            match rhs.div_exact(row[pc]) {
+                Some(value) if accept(value) => x[pc] = value,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// A basis of the solutions of `A * x = 0`, one vector per free variable.
+    ///
+    /// Each vector sets its free variable to the product of the pivots, which
+    /// keeps the other entries integral, and is then divided by its GCD.
+    /// Integer matrices with large pivots may overflow.
+    pub fn nullspace(&self, echelon: &Echelon) -> Vec<Vec<T>> {
+        // `gcd(p, 0)` is `|p|`, so free variables come out positive.
+        let scale = (0..echelon.rank())
+            .map(|r| self[(r, echelon.pivot_cols[r])].gcd(T::ZERO))
+            .fold(T::ONE, T::mul);
+        echelon
+            .free_cols
+            .iter()
+            .map(|&free| {
+                let mut x = vec![T::ZERO; echelon.vars];
+                x[free] = scale;
+                let exact = self.substitute(echelon, &mut x, false, |_| true);
+                debug_assert!(exact, "Product of the pivots divides by every pivot");
+                let g = x.iter().fold(T::ZERO, |g, &v| g.gcd(v));
+                x.iter_mut().for_each(|v| *v = v.div_exact(g).unwrap());
+                x
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(col < self.cols, "Column {col} out of bounds");
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(col < self.cols, "Column {col} out of bounds");
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mul(a: &Matrix<i64>, x: &[i64]) -> Vec<i64> {
+        (0..a.rows())
+            .map(|r| a.row(r).iter().zip(x).map(|(a, x)| a * x).sum())
+            .collect()
+    }
+
+    #[test]
+    fn test_construction_and_rows() {
+        let mut m = Matrix::from_rows([[1, 2], [3, 4], [5, 6]]);
+        assert_eq!((m.rows(), m.cols()), (3, 2));
+        m.swap_rows(2, 0);
+        assert_eq!(m.row(0), [5, 6]);
+        assert_eq!(m.row(2), [1, 2]);
+        m.add_scaled_row(1, 2, -3);
+        m.scale_row(0, 2);
+        assert_eq!(m, Matrix::from_rows([[10, 12], [0, -2], [1, 2]]));
+        assert_eq!(m.augment(&[7, 8, 9]).row(1), [0, -2, 8]);
+        assert_eq!(m.get(3, 0), None);
+    }
+
+    #[test]
+    fn test_integer_system() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let a = Matrix::from_rows([[1, 1, 1], [0, 2, 5], [2, 5, -1]]);
+        let mut m = a.augment(&[6, -4, 27]);
+        let echelon = m.eliminate(3);
+        assert_eq!(echelon.rank(), 3);
+        assert!(echelon.free_cols.is_empty() && echelon.consistent);
+        let mut x = [0; 3];
+        assert!(m.back_substitute(&echelon, &mut x));
+        assert_eq!(x, [5, 3, -2]);
+        assert!(!m.back_substitute_while(&echelon, &mut [0; 3], |v| v >= 0));
+
+        // 2x = 3 has no integral solution.
+        let mut m = Matrix::from_rows([[2, 3]]);
+        let echelon = m.eliminate(1);
+        assert!(!m.back_substitute(&echelon, &mut [0]));
+
+        // x + y = 1, x + y = 2 is inconsistent.
+        let mut m = Matrix::from_rows([[1, 1, 1], [1, 1, 2]]);
+        assert!(!m.eliminate(2).consistent);
+    }
+
+    #[test]
+    fn test_nullspace() {
+        let a = Matrix::from_rows([[2, 4, 1, 3], [4, 8, 3, 7], [2, 4, 2, 4]]);
+        let mut m = a.clone();
+        let echelon = m.eliminate(4);
+        assert_eq!(echelon.pivot_cols, [0, 2]);
+        assert_eq!(echelon.free_cols, [1, 3]);
+        let basis = m.nullspace(&echelon);
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert_eq!(mul(&a, v), [0, 0, 0]);
+        }
+        assert_eq!(basis[0], [-2, 1, 0, 0]);
+        assert_eq!(basis[1], [-1, 0, -1, 1]);
+    }
+
+    #[test]
+    fn test_gf2() {
+        // x0 ^ x1 = 1, x1 ^ x2 = 0, x0 ^ x2 = 1
+        let a = Matrix::from_rows([
+            [true, true, false],
+            [false, true, true],
+            [true, false, true],
+        ]);
+        let mut m = a.augment(&[true, false, true]);
+        let echelon = m.eliminate(3);
+        assert!(echelon.consistent);
+        assert_eq!(echelon.free_cols, [2]);
+        let mut x = [false; 3];
+        assert!(m.back_substitute(&echelon, &mut x));
+        assert_eq!(x, [true, false, false]);
+        assert_eq!(m.nullspace(&echelon), [vec![true, true, true]]);
+
+        let mut m = a.augment(&[true, true, true]);
+        assert!(!m.eliminate(3).consistent);
+    }
+}
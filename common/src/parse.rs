@@ -0,0 +1,266 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Error of a line parser, e.g. one generated by `#[derive(AocParse)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input, if known.
+    pub line: Option<usize>,
+    /// 1-based column (in bytes) within the line.
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Sets the line the error occurred on.
+    pub const fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}, column {}: {}", self.column, self.message),
+            None => write!(f, "column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Types parsed from one line of input, usually derived with `#[derive(AocParse)]`.
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc(format = "{x},{y},{z}")]
+/// pub struct Pt {
+///     pub x: i64,
+///     pub y: i64,
+///     pub z: i64,
+/// }
+///
+/// let points = Pt::parse_lines(input)?;
+/// ```
+pub trait AocParse: Sized {
+    /// The format pattern the type was derived with.
+    const FORMAT: &'static str;
+
+    fn parse_line(line: &str) -> Result<Self, ParseError>;
+
+    /// Parses every non-empty line of `input`, reporting errors with their line number.
+    fn parse_lines(input: &str) -> Result<Vec<Self>, ParseError> {
+        lines_with(input, Self::parse_line)
+    }
+}
+
+/// Parses every non-empty line of `input` with `FromStr`, like [`AocParse::parse_lines`].
+///
+/// For types such as `common::point::Point3` whose `FromStr` reports a [`ParseError`].
+pub fn lines<T: FromStr<Err = ParseError>>(input: &str) -> Result<Vec<T>, ParseError> {
+    lines_with(input, str::parse)
+}
+
+/// Parses every non-empty line of `input` with `parse`, adding line numbers to its errors.
+pub fn lines_with<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/// Cursor over a line, used by the code generated by `#[derive(AocParse)]`.
+pub struct Scanner<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub const fn new(line: &'a str) -> Self {
+        Self { line, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    /// 1-based column of the next character.
+    pub const fn column(&self) -> usize {
+        self.pos + 1
+    }
+
+    /// Consumes `literal`, which must come next.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if !self.rest().starts_with(literal) {
+            return Err(ParseError::new(
+                self.column(),
+                format!("expected `{literal}`, found `{}`", self.rest()),
+            ));
+        }
+        self.pos += literal.len();
+        Ok(())
+    }
+
+    /// Consumes `literal` if it comes next, returning whether it did.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    /// Text of field `name`: up to the next `until`, or the rest of the line.
+    fn take(&mut self, name: &str, until: Option<&str>) -> Result<(usize, &'a str), ParseError> {
+        let rest = self.rest();
+        let len = match until {
+            Some(until) => rest.find(until).ok_or_else(|| {
+                ParseError::new(
+                    self.column(),
+                    format!("expected `{until}` after `{name}` in `{rest}`"),
+                )
+            })?,
+            None => rest.len(),
+        };
+        let start = self.pos;
+        self.pos += len;
+        Ok((start, &rest[..len]))
+    }
+
+    /// Parses field `name` from the text up to the next `until`, or the rest of the line.
+    pub fn field<T>(&mut self, name: &str, until: Option<&str>) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (start, text) = self.take(name, until)?;
+        parse_item(name, text, start + 1)
+    }
+
+    /// Parses field `name` as a list of items separated by `sep`, skipping empty items.
+    pub fn list<T>(
+        &mut self,
+        name: &str,
+        until: Option<&str>,
+        sep: &str,
+    ) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (start, text) = self.take(name, until)?;
+        let mut items = Vec::new();
+        let mut offset = 0;
+        for item in text.split(sep) {
+            if !item.is_empty() {
+                items.push(parse_item(name, item, start + offset + 1)?);
+            }
+            offset += item.len() + sep.len();
+        }
+        Ok(items)
+    }
+
+    /// Checks that the whole line was consumed.
+    pub fn finish(self) -> Result<(), ParseError> {
+        match self.rest() {
+            "" => Ok(()),
+            rest => Err(ParseError::new(
+                self.column(),
+                format!("unexpected `{rest}` at end of line"),
+            )),
+        }
+    }
+}
+
+fn parse_item<T>(name: &str, text: &str, column: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|e| ParseError::new(column, format!("invalid `{name}` `{text}`: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Range {
+        start: u64,
+        end: u64,
+    }
+
+    // What `#[derive(AocParse)]` generates for `#[aoc(format = "{start}-{end}")]`.
+    impl AocParse for Range {
+        const FORMAT: &'static str = "{start}-{end}";
+
+        fn parse_line(line: &str) -> Result<Self, ParseError> {
+            let mut scanner = Scanner::new(line);
+            let start = scanner.field("start", Some("-"))?;
+            scanner.literal("-")?;
+            let end = scanner.field("end", None)?;
+            scanner.finish()?;
+            Ok(Self { start, end })
+        }
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let ranges = Range::parse_lines("3-5\n\n10-14\r\n").unwrap();
+        let ranges: Vec<_> = ranges.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(ranges, [(3, 5), (10, 14)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = Range::parse_lines("3-5\n10-x4").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: invalid `end` `x4`: invalid digit found in string"
+        );
+        let err = Range::parse_line("35").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "column 1: expected `-` after `start` in `35`"
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let mut scanner = Scanner::new("aaa: 1 2  3");
+        assert_eq!(scanner.field::<String>("src", Some(": ")).unwrap(), "aaa");
+        scanner.literal(": ").unwrap();
+        assert_eq!(scanner.list::<u8>("dests", None, " ").unwrap(), [1, 2, 3]);
+        scanner.finish().unwrap();
+
+        let mut scanner = Scanner::new("(1,2) (3)");
+        let mut groups = Vec::new();
+        while scanner.eat("(") {
+            groups.push(scanner.list::<u8>("group", Some(")"), ",").unwrap());
+            scanner.literal(")").unwrap();
+            scanner.eat(" ");
+        }
+        scanner.finish().unwrap();
+        assert_eq!(groups, [vec![1, 2], vec![3]]);
+
+        let mut scanner = Scanner::new("1 x");
+        let err = scanner.list::<u8>("dests", None, " ").unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+}
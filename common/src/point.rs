@@ -0,0 +1,375 @@
+use crate::parse::ParseError;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Integer types usable as point coordinates.
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + FromStr
+{
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+
+    /// `|self - other|`, also for unsigned types.
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_coord!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Point or vector with `N` integer coordinates.
+///
+/// Ordering is lexicographic over the coordinates, `x` first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<T, const N: usize>(pub [T; N]);
+
+/// The origin.
+impl<T: Coord, const N: usize> Default for PointN<T, N> {
+    fn default() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+pub type Point2<T> = PointN<T, 2>;
+pub type Point3<T> = PointN<T, 3>;
+
+impl<T: Copy> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self([x, y])
+    }
+
+    pub const fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub const fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self([x, y, z])
+    }
+
+    pub const fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub const fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub const fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T: Coord, const N: usize> PointN<T, N> {
+    fn zip(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        (0..N).fold(T::default(), |sum, i| sum + self.0[i].abs_diff(other.0[i]))
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        (0..N)
+            .map(|i| self.0[i].abs_diff(other.0[i]))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Squared Euclidean distance, exact for integers.
+    pub fn euclidean_sq(&self, other: &Self) -> T {
+        (0..N).fold(T::default(), |sum, i| {
+            let d = self.0[i].abs_diff(other.0[i]);
+            sum + d * d
+        })
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        (0..N)
+            .map(|i| self.0[i].abs_diff(other.0[i]).to_f64().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// The `2 * N` points one step away along a single axis.
+    ///
+    /// Steps that would overflow the coordinate type, e.g. below 0 for
+    /// unsigned types, are skipped.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
+        let p = *self;
+        (0..N).flat_map(move |axis| {
+            [p.0[axis].checked_sub(T::ONE), p.0[axis].checked_add(T::ONE)]
+                .into_iter()
+                .flatten()
+                .map(move |c| {
+                    let mut q = p;
+                    q.0[axis] = c;
+                    q
+                })
+        })
+    }
+
+    /// The `3^N - 1` points differing by at most one in every coordinate.
+    ///
+    /// Steps that would overflow the coordinate type are skipped.
+    pub fn neighbors_diagonal(&self) -> impl Iterator<Item = Self> {
+        let p = *self;
+        (0..3usize.pow(N as u32)).filter_map(move |mut k| {
+            let mut q = p;
+            let mut moved = false;
+            for c in &mut q.0 {
+                *c = match k % 3 {
+                    0 => c.checked_sub(T::ONE)?,
+                    1 => *c,
+                    _ => c.checked_add(T::ONE)?,
+                };
+                moved |= k % 3 != 1;
+                k /= 3;
+            }
+            moved.then_some(q)
+        })
+    }
+}
+
+impl<T: Coord + Neg<Output = T>, const N: usize> PointN<T, N> {
+    /// Rotates by 90 degrees in the plane of axes `from` and `to`, turning `from` into `to`.
+    pub fn rotate(&self, from: usize, to: usize) -> Self {
+        let mut q = *self;
+        q.0[to] = self.0[from];
+        q.0[from] = -self.0[to];
+        q
+    }
+
+    /// Mirrors the point along `axis`, negating that coordinate.
+    pub fn reflect(&self, axis: usize) -> Self {
+        let mut q = *self;
+        q.0[axis] = -q.0[axis];
+        q
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point2<T> {
+    /// Quarter turn clockwise with `y` pointing down, as on screen: `(x, y) -> (-y, x)`.
+    pub fn rotate_cw(&self) -> Self {
+        self.rotate(0, 1)
+    }
+
+    /// Quarter turn counter-clockwise with `y` pointing down: `(x, y) -> (y, -x)`.
+    pub fn rotate_ccw(&self) -> Self {
+        self.rotate(1, 0)
+    }
+
+    /// Mirrors left to right: `(x, y) -> (-x, y)`.
+    pub fn flip_x(&self) -> Self {
+        self.reflect(0)
+    }
+
+    /// Mirrors top to bottom: `(x, y) -> (x, -y)`.
+    pub fn flip_y(&self) -> Self {
+        self.reflect(1)
+    }
+}
+
+impl<T: Coord, const N: usize> Add for PointN<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip(other, |a, b| a + b)
+    }
+}
+
+impl<T: Coord, const N: usize> Sub for PointN<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl<T: Coord, const N: usize> Mul<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Self(self.0.map(|c| c * k))
+    }
+}
+
+impl<T: Coord + Neg<Output = T>, const N: usize> Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<T: Coord, const N: usize> AddAssign for PointN<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Coord, const N: usize> SubAssign for PointN<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+/// Parses `N` comma separated coordinates, e.g. `162,817,812`.
+impl<T: Coord, const N: usize> FromStr for PointN<T, N>
+where
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut coords = [T::default(); N];
+        let mut parts = s.split(',');
+        let mut column = 1;
+        for (axis, coord) in coords.iter_mut().enumerate() {
+            let part = parts.next().ok_or_else(|| {
+                ParseError::new(
+                    s.len() + 1,
+                    format!("expected {N} coordinates, found {axis}"),
+                )
+            })?;
+            *coord = part.trim().parse().map_err(|e| {
+                ParseError::new(column, format!("invalid coordinate `{part}`: {e}"))
+            })?;
+            column += part.len() + 1;
+        }
+        if parts.next().is_some() {
+            return Err(ParseError::new(
+                column - 1,
+                format!("expected {N} coordinates"),
+            ));
+        }
+        Ok(Self(coords))
+    }
+}
+
+impl<T: Display, const N: usize> Display for PointN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops_and_distances() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, 1);
+        assert_eq!(a + b, Point2::new(5, 6));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(-(a * 2), Point2::new(-2, -10));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean_sq(&b), 25);
+        assert_eq!(a.euclidean(&b), 5.0);
+
+        let p = Point3::<u32>::new(3, 0, 7);
+        let q = Point3::new(1, 2, 7);
+        assert_eq!(p.manhattan(&q), 4);
+        assert_eq!(p.euclidean_sq(&q), 8);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let p = Point2::new(2, 1);
+        assert_eq!(p.rotate_cw(), Point2::new(-1, 2));
+        assert_eq!(p.rotate_ccw(), Point2::new(1, -2));
+        assert_eq!(p.rotate_cw().rotate_ccw(), p);
+        assert_eq!(p.rotate_cw().rotate_cw(), -p);
+        assert_eq!(p.flip_x(), Point2::new(-2, 1));
+        assert_eq!(p.flip_y(), Point2::new(2, -1));
+        assert_eq!(Point3::new(1, 2, 3).rotate(2, 0), Point3::new(3, 2, -1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Point2::<u8>::new(0, 0);
+        assert_eq!(
+            origin.neighbors().collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(origin.neighbors_diagonal().count(), 3);
+        assert_eq!(Point2::new(5, 5).neighbors().count(), 4);
+        assert_eq!(Point2::new(5, 5).neighbors_diagonal().count(), 8);
+        assert_eq!(Point3::new(0i64, 0, 0).neighbors_diagonal().count(), 26);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let p: Point3<i64> = "162,-817,812".parse().unwrap();
+        assert_eq!(p, Point3::new(162, -817, 812));
+        assert_eq!(p.to_string(), "162,-817,812");
+
+        let err = "1,x".parse::<Point2<u32>>().unwrap_err();
+        assert_eq!(err.column, 3);
+        assert!("1,2,3".parse::<Point2<u32>>().is_err());
+        assert!("1".parse::<Point2<u32>>().is_err());
+
+        let points = crate::parse::lines::<Point2<u32>>("1,2\n\n3,4\n").unwrap();
+        assert_eq!(points, [Point2::new(1, 2), Point2::new(3, 4)]);
+        let err = crate::parse::lines::<Point2<u32>>("1,2\n3,-4").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: invalid coordinate `-4`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use crate::error::AocError;
+use crate::point::Point2;
+
+/// Simple polygon whose edges are all horizontal or vertical, e.g. a loop of
+/// tiles through the vertices in order.
+///
+/// Points on the boundary count as inside. Construction compresses the vertex
+/// coordinates into a grid of the vertex lines and the gaps between them, each
+/// cell wholly inside or outside, so point and rectangle queries take a few
+/// binary searches. The grid takes memory quadratic in the number of distinct
+/// coordinates.
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point2<i64>>,
+    /// Distinct vertex coordinates, sorted.
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// Number of compressed columns, `2 * xs.len() - 1`.
+    cols: usize,
+    /// Prefix sums of the compressed cells outside the polygon, with an extra
+    /// leading row and column of zeros.
+    outside: Vec<u32>,
+}
+
+impl RectilinearPolygon {
+    /// Polygon through `vertices` in order, closed by an edge back to the first.
+    ///
+    /// Fails unless there are at least 4 vertices, every edge is horizontal or
+    /// vertical and no two edges meet except consecutive ones at their vertex.
+    pub fn new(vertices: Vec<Point2<i64>>) -> Result<Self, AocError> {
+        validate(&vertices)?;
+        let mut xs: Vec<i64> = vertices.iter().map(Point2::x).collect();
+        let mut ys: Vec<i64> = vertices.iter().map(Point2::y).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+        let cols = 2 * xs.len() - 1;
+        let rows = 2 * ys.len() - 1;
+
+        let mut polygon = Self {
+            vertices,
+            xs,
+            ys,
+            cols,
+            outside: Vec::new(),
+        };
+        let inside = polygon.compressed_cells(rows);
+
+        let mut outside = vec![0; (cols + 1) * (rows + 1)];
+        for r in 0..rows {
+            for c in 0..cols {
+                outside[(r + 1) * (cols + 1) + c + 1] = u32::from(!inside[r * cols + c])
+                    + outside[r * (cols + 1) + c + 1]
+                    + outside[(r + 1) * (cols + 1) + c]
+                    - outside[r * (cols + 1) + c];
+            }
+        }
+        polygon.outside = outside;
+        Ok(polygon)
+    }
+
+    /// Whether each compressed cell is inside, row by row.
+    fn compressed_cells(&self, rows: usize) -> Vec<bool> {
+        let x_index = |x| self.xs.binary_search(&x).unwrap();
+        let y_index = |y| self.ys.binary_search(&y).unwrap();
+        let mut inside = vec![false; self.cols * rows];
+
+        let mut vertical = Vec::new();
+        for (a, b) in self.edges() {
+            let (c0, c1) = (2 * x_index(a.x()), 2 * x_index(b.x()));
+            let (r0, r1) = (2 * y_index(a.y()), 2 * y_index(b.y()));
+            for r in r0.min(r1)..=r0.max(r1) {
+                for c in c0.min(c1)..=c0.max(c1) {
+                    inside[r * self.cols + c] = true;
+                }
+            }
+            if c0 == c1 {
+                vertical.push((c0 / 2, a.y().min(b.y()), a.y().max(b.y())));
+            }
+        }
+
+        // Ray casting along each row, in doubled coordinates so the gaps
+        // between vertex lines have integral midpoints. Edges count from
+        // their lower end up to but excluding their upper end.
+        let mut crossings = vec![false; self.xs.len()];
+        for r in 0..rows {
+            let y2 = self.ys[r / 2] + self.ys[r.div_ceil(2)];
+            crossings.fill(false);
+            for &(i, lo, hi) in &vertical {
+                if 2 * lo <= y2 && y2 < 2 * hi {
+                    crossings[i] ^= true;
+                }
+            }
+            let mut parity = false;
+            for c in 0..self.cols {
+                inside[r * self.cols + c] |= parity;
+                if c % 2 == 0 && crossings[c / 2] {
+                    parity = !parity;
+                }
+            }
+        }
+        inside
+    }
+
+    pub fn vertices(&self) -> &[Point2<i64>] {
+        &self.vertices
+    }
+
+    /// Edges as pairs of consecutive vertices, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+        edges(&self.vertices)
+    }
+
+    /// Enclosed area, by the shoelace formula.
+    pub fn area(&self) -> i64 {
+        let twice: i128 = self
+            .edges()
+            .map(|(a, b)| {
+                i128::from(a.x()) * i128::from(b.y()) - i128::from(b.x()) * i128::from(a.y())
+            })
+            .sum();
+        i64::try_from(twice.abs() / 2).expect("Polygon area overflows i64")
+    }
+
+    pub fn perimeter(&self) -> i64 {
+        self.edges().map(|(a, b)| a.manhattan(&b)).sum()
+    }
+
+    /// Number of integer points inside or on the boundary, by Pick's theorem.
+    pub fn lattice_points(&self) -> i64 {
+        self.area() + self.perimeter() / 2 + 1
+    }
+
+    /// Compressed index of coordinate `v`: `2 * i` for `coords[i]`, odd for the
+    /// gaps between, `None` outside the bounding box.
+    fn index(coords: &[i64], v: i64) -> Option<usize> {
+        match coords.binary_search(&v) {
+            Ok(i) => Some(2 * i),
+            Err(i) if i == 0 || i == coords.len() => None,
+            Err(i) => Some(2 * i - 1),
+        }
+    }
+
+    /// Number of compressed cells outside the polygon in columns `c0..=c1` and rows `r0..=r1`.
+    fn outside_cells(&self, c0: usize, c1: usize, r0: usize, r1: usize) -> u32 {
+        let at = |r: usize, c: usize| self.outside[r * (self.cols + 1) + c];
+        at(r1 + 1, c1 + 1) + at(r0, c0) - at(r0, c1 + 1) - at(r1 + 1, c0)
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary.
+    pub fn contains_point(&self, p: Point2<i64>) -> bool {
+        match (Self::index(&self.xs, p.x()), Self::index(&self.ys, p.y())) {
+            (Some(c), Some(r)) => self.outside_cells(c, c, r, r) == 0,
+            _ => false,
+        }
+    }
+
+    /// Whether the rectangle with opposite corners `a` and `b`, edges
+    /// included, lies inside the polygon or on its boundary.
+    pub fn contains_rect(&self, a: Point2<i64>, b: Point2<i64>) -> bool {
+        let (x0, x1) = (a.x().min(b.x()), a.x().max(b.x()));
+        let (y0, y1) = (a.y().min(b.y()), a.y().max(b.y()));
+        let corners = (
+            Self::index(&self.xs, x0),
+            Self::index(&self.xs, x1),
+            Self::index(&self.ys, y0),
+            Self::index(&self.ys, y1),
+        );
+        match corners {
+            (Some(c0), Some(c1), Some(r0), Some(r1)) => self.outside_cells(c0, c1, r0, r1) == 0,
+            _ => false,
+        }
+    }
+}
+
+fn edges(vertices: &[Point2<i64>]) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn validate(vertices: &[Point2<i64>]) -> Result<(), AocError> {
+    if vertices.len() < 4 {
+        return Err(AocError::new(format!(
+            "a rectilinear polygon needs at least 4 vertices, found {}",
+            vertices.len()
+        )));
+    }
+    let edges: Vec<_> = edges(vertices).collect();
+    for &(a, b) in &edges {
+        if a == b {
+            return Err(AocError::new(format!("vertex {a} repeats")));
+        }
+        if a.x() != b.x() && a.y() != b.y() {
+            return Err(AocError::new(format!(
+                "edge from {a} to {b} is not horizontal or vertical"
+            )));
+        }
+    }
+
+    let n = edges.len();
+    for i in 0..n {
+        // Consecutive edges share a vertex, and must not double back.
+        let (a, b) = edges[i];
+        let (_, c) = edges[(i + 1) % n];
+        let back = (b.x() - a.x()).signum() * (c.x() - b.x()).signum()
+            + (b.y() - a.y()).signum() * (c.y() - b.y()).signum();
+        if back < 0 {
+            return Err(AocError::new(format!("edges turn back at {b}")));
+        }
+        // Other edges must not meet at all. Axis-aligned segments meet where
+        // their bounding boxes overlap.
+        for (j, &(c, d)) in edges.iter().enumerate().skip(i + 2) {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if a.x().min(b.x()) <= c.x().max(d.x())
+                && c.x().min(d.x()) <= a.x().max(b.x())
+                && a.y().min(b.y()) <= c.y().max(d.y())
+                && c.y().min(d.y()) <= a.y().max(b.y())
+            {
+                return Err(AocError::new(format!(
+                    "edges {a} to {b} and {c} to {d} intersect"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Result<RectilinearPolygon, AocError> {
+        RectilinearPolygon::new(vertices.iter().map(|&(x, y)| Point2::new(x, y)).collect())
+    }
+
+    // The loop of day 9's example.
+    const EXAMPLE: [(i64, i64); 8] = [
+        (7, 1),
+        (11, 1),
+        (11, 7),
+        (9, 7),
+        (9, 5),
+        (2, 5),
+        (2, 3),
+        (7, 3),
+    ];
+
+    #[test]
+    fn test_measures() {
+        let poly = polygon(&EXAMPLE).unwrap();
+        assert_eq!(poly.area(), 4 * 2 + 9 * 2 + 2 * 2);
+        assert_eq!(poly.perimeter(), 4 + 6 + 2 + 2 + 7 + 2 + 5 + 2);
+        assert_eq!(poly.edges().count(), 8);
+        assert_eq!(
+            polygon(&[(0, 0), (0, 2), (3, 2), (3, 0)])
+                .unwrap()
+                .lattice_points(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let poly = polygon(&EXAMPLE).unwrap();
+        let p = |x, y| Point2::new(x, y);
+        assert!(poly.contains_point(p(7, 1)));
+        assert!(poly.contains_point(p(8, 4)));
+        assert!(poly.contains_point(p(2, 4)));
+        assert!(poly.contains_point(p(10, 6)));
+        assert!(!poly.contains_point(p(8, 6)));
+        assert!(!poly.contains_point(p(3, 2)));
+        assert!(!poly.contains_point(p(12, 4)));
+
+        assert!(poly.contains_rect(p(9, 5), p(2, 3)));
+        assert!(poly.contains_rect(p(7, 1), p(11, 5)));
+        assert!(poly.contains_rect(p(11, 7), p(9, 1)));
+        assert!(!poly.contains_rect(p(2, 5), p(11, 1)));
+        assert!(!poly.contains_rect(p(7, 3), p(11, 7)));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(polygon(&[(0, 0), (0, 1), (1, 1)]).is_err());
+        assert!(polygon(&[(0, 0), (0, 2), (2, 2), (2, 1), (1, 0)]).is_err());
+        // Doubles back along its first edge.
+        assert!(polygon(&[(0, 0), (4, 0), (2, 0), (2, 2), (0, 2)]).is_err());
+        // A figure eight.
+        let err = polygon(&[(0, 0), (2, 0), (2, 2), (1, 2), (1, -1), (0, -1)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "edges 0,0 to 2,0 and 1,2 to 1,-1 intersect"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::RangeInclusive;
+
+/// Integer types a [`RangeSet`] can hold.
+pub trait Discrete: Copy + Ord {
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// Number of values in `start..=end`, with `start <= end`.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            fn checked_succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn count(start: Self, end: Self) -> u128 {
+                (end as i128 - start as i128) as u128 + 1
+            }
+        }
+    )*};
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Set of integers stored as sorted, disjoint inclusive ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, so `1..=2` and
+/// `3..=4` become `1..=4`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    /// Sorted `(start, end)` pairs, separated by at least one missing value.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from ranges already sorted by start, merging as needed.
+    fn from_sorted(ranges: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut merged: Vec<(T, T)> = Vec::new();
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if last.1.checked_succ().is_none_or(|next| start <= next) => {
+                    last.1 = last.1.max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Adds `range`, merging it with overlapping or adjacent ranges. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // Ranges that end before `start - 1` and start after `end + 1` are untouched.
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.checked_succ().is_some_and(|next| next < start));
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| end.checked_succ().is_none_or(|next| s <= next));
+        let merged = match self.ranges[first..last] {
+            [] => (start, end),
+            [(s, _), ..] => (s.min(start), self.ranges[last - 1].1.max(end)),
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Whether `value` is in the set, in O(log n).
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| T::count(s, e)).sum()
+    }
+
+    /// The disjoint ranges of the set, in increasing order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// The missing ranges between the first and last value of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+        self.ranges.windows(2).map(|w| {
+            // Ranges are separated by at least one value, so neither overflows.
+            w[0].1.checked_succ().unwrap()..=w[1].0.checked_pred().unwrap()
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut all = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if x.0 <= y.0 {
+                all.extend(a.next());
+            } else {
+                all.extend(b.next());
+            }
+        }
+        all.extend(a.chain(b));
+        Self::from_sorted(all)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(s1, e1)), Some(&(s2, e2))) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for &(start, end) in &self.ranges {
+            // Skip ranges of `other` entirely before this one.
+            while other.ranges.get(j).is_some_and(|&(_, e)| e < start) {
+                j += 1;
+            }
+            // Start of the part of this range not yet covered by `other`.
+            let mut next = Some(start);
+            for &(os, oe) in other.ranges[j..].iter().take_while(|r| r.0 <= end) {
+                let Some(s) = next else {
+                    break;
+                };
+                if os > s {
+                    ranges.push((s, os.checked_pred().unwrap()));
+                }
+                next = oe.checked_succ().filter(|&n| n <= end);
+            }
+            if let Some(s) = next {
+                ranges.push((s, end));
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<(T, T)> = iter
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(s, e)| s <= e)
+            .collect();
+        ranges.sort_unstable();
+        Self::from_sorted(ranges)
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> RangeSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &RangeSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_normalise() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let s = set(&[10..=14, 3..=5, 16..=20, 12..=18, 6..=6, 30..=29]);
+        assert_eq!(ranges(&s), [3..=6, 10..=20]);
+        assert_eq!(s.len(), 15);
+        assert_eq!(s.gaps().collect::<Vec<_>>(), [7..=9]);
+
+        let full: RangeSet<u8> = [0..=100, 101..=255].into_iter().collect();
+        assert_eq!(full.len(), 256);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[1..=2, 5..=6, 10..=12]);
+        s.insert(8..=8);
+        assert_eq!(ranges(&s), [1..=2, 5..=6, 8..=8, 10..=12]);
+        s.insert(3..=9);
+        assert_eq!(ranges(&s), [1..=12]);
+        s.insert(i32::MAX - 1..=i32::MAX);
+        s.insert(0..=0);
+        assert_eq!(ranges(&s), [0..=12, i32::MAX - 1..=i32::MAX]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let s = set(&[3..=5, 10..=14]);
+        let hits: Vec<_> = (0..16).filter(|&v| s.contains(v)).collect();
+        assert_eq!(hits, [3, 4, 5, 10, 11, 12, 13, 14]);
+        assert!(!RangeSet::new().contains(0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=5, 10..=20]);
+        let b = set(&[4..=12, 15..=15, 19..=25]);
+        assert_eq!(ranges(&a.union(&b)), [1..=25]);
+        assert_eq!(
+            ranges(&a.intersection(&b)),
+            [4..=5, 10..=12, 15..=15, 19..=20]
+        );
+        assert_eq!(ranges(&a.difference(&b)), [1..=3, 13..=14, 16..=18]);
+        assert_eq!(ranges(&b.difference(&a)), [6..=9, 21..=25]);
+
+        let max = set(&[0..=i32::MAX]);
+        assert!(a.difference(&max).is_empty());
+        assert_eq!(ranges(&max.difference(&a)), [0..=0, 6..=9, 21..=i32::MAX]);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::answers::{self, Answers, Check};
+use crate::error::AocError;
+use crate::{alloc, input, timing};
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Once;
+use std::time::Duration;
+
+pub use inventory;
+
+/// The parser of a day registered with `#[aoc_parse]`.
+pub struct Day {
+    pub day: u8,
+    /// Name of the annotated function.
+    pub name: &'static str,
+    /// Parses the raw puzzle input into the value shared by the parts.
+    pub parse: fn(&str) -> Result<Box<dyn Any>, AocError>,
+}
+
+inventory::collect!(Day);
+
+/// A solver registered with `#[aoc_part(n)]`.
+pub struct Part {
+    pub day: u8,
+    pub part: u8,
+    /// Name of the annotated function.
+    pub name: &'static str,
+    /// Solves the part from the value parsed by the [`Day`] parser.
+    pub solve: fn(&mut dyn Any) -> Result<String, AocError>,
+}
+
+inventory::collect!(Part);
+
+/// All registered day parsers, ordered by day.
+pub fn days() -> Vec<&'static Day> {
+    let mut days: Vec<_> = inventory::iter::<Day>.into_iter().collect();
+    days.sort_by_key(|d| d.day);
+    days
+}
+
+/// The registered parser of `day`, if any.
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    inventory::iter::<Day>.into_iter().find(|d| d.day == day)
+}
+
+/// All registered parts, ordered by day and part.
+pub fn parts() -> Vec<&'static Part> {
+    let mut parts: Vec<_> = inventory::iter::<Part>.into_iter().collect();
+    parts.sort_by_key(|p| (p.day, p.part));
+    parts
+}
+
+/// Registered parts of `day`, ordered by part.
+pub fn day_parts(day: u8) -> Vec<&'static Part> {
+    parts().into_iter().filter(|p| p.day == day).collect()
+}
+
+/// The registered solver of `part` of `day`, if any.
+pub fn find(day: u8, part: u8) -> Option<&'static Part> {
+    inventory::iter::<Part>
+        .into_iter()
+        .find(|p| p.day == day && p.part == part)
+}
+
+/// Answer of one part in a [`DayRun`].
+pub struct PartRun {
+    pub part: &'static Part,
+    /// The answer, or why the part failed.
+    pub answer: Result<String, AocError>,
+    pub time: Duration,
+}
+
+/// Result of [`run`]: the parse time and every part that was run.
+pub struct DayRun {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    /// Time spent parsing and solving.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+thread_local! {
+    /// Message of a panic inside [`catch`] on this thread, `None` outside of it.
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Wraps the panic hook so panics inside [`catch`] record their message for
+/// the error instead of printing it, and other panics print as before.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let recorded = CAUGHT.with_borrow_mut(|caught| {
+                let caught = caught.as_mut()?;
+                let message = info.payload_as_str().unwrap_or("non-string payload");
+                *caught = info.location().map_or_else(
+                    || format!(": {message}"),
+                    |location| format!(" at {location}: {message}"),
+                );
+                Some(())
+            });
+            if recorded.is_none() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into an error of `day` about `what` that carries
+/// the panic message instead of printing a backtrace.
+fn catch<T>(
+    day: u8,
+    what: impl Display,
+    f: impl FnOnce() -> Result<T, AocError>,
+) -> Result<T, AocError> {
+    install_panic_hook();
+    CAUGHT.set(Some(String::new()));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let message = CAUGHT.take().unwrap_or_default();
+    result
+        .unwrap_or_else(|_| Err(AocError::new(format!("{what} panicked{message}"))))
+        .map_err(|e| e.with_day(day))
+}
+
+/// Parses `input` once with the registered parser of `day` and runs `parts`
+/// (parts of the same day) on the parsed value, in order.
+///
+/// Parts may modify the parsed value, so they should be given in part order.
+/// Fails if the day has no parser or the input is invalid; a part that fails
+/// or panics is reported in its [`PartRun`]. Errors carry the day.
+pub fn run(day: u8, input: &str, parts: &[&'static Part]) -> Result<DayRun, AocError> {
+    let Some(parser) = find_day(day) else {
+        return Err(AocError::new("no parser registered with #[aoc_parse]").with_day(day));
+    };
+    let (parsed, parse_time) = timing::timed(|| {
+        catch(day, format_args!("parser {}", parser.name), || {
+            (parser.parse)(input)
+        })
+    });
+    let mut parsed = parsed?;
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (answer, time) = timing::timed(|| {
+                catch(
+                    day,
+                    format_args!("part {} ({})", part.part, part.name),
+                    || (part.solve)(&mut *parsed),
+                )
+            });
+            PartRun { part, answer, time }
+        })
+        .collect();
+    Ok(DayRun { parse_time, parts })
+}
+
+/// Asserts that the registered `part` of `day` answers `expected` for `input`,
+/// [normalised](input::normalize) like the input of the binaries.
+///
+/// This is what the tests generated by `aoc_test!` call.
+///
+/// # Panics
+/// If the part is not registered, rejects the input or its answer differs.
+pub fn check(day: u8, part: u8, input: &str, expected: impl Display) {
+    let Some(registered) = find(day, part) else {
+        panic!("Day {day} part {part} is not registered with #[aoc_part({part})]");
+    };
+    let input = input::normalize(input);
+    let run = run(day, &input, &[registered]).unwrap_or_else(|e| panic!("Invalid input: {e}"));
+    let answer = run.parts.into_iter().next().map(|p| p.answer);
+    let answer = answer
+        .expect("one part run")
+        .unwrap_or_else(|e| panic!("Invalid input: {e}"));
+    assert_eq!(
+        answer,
+        expected.to_string(),
+        "Day {day} part {part} ({})",
+        registered.name
+    );
+}
+
+/// Like [`check`] with the input read from `path`, skipped if the file does not exist.
+///
+/// Puzzle inputs are not committed, so regression tests against them only run
+/// where the input is present.
+pub fn check_file(day: u8, part: u8, path: &str, expected: impl Display) {
+    let path = Path::new(path);
+    if !path.is_file() {
+        eprintln!(
+            "Skipping day {day} part {part}: {} not found",
+            path.display()
+        );
+        return;
+    }
+    let input = input::read(path).unwrap_or_else(|e| panic!("Cannot read {}: {e}", path.display()));
+    check(day, part, &input, expected);
+}
+
+/// Entry point generated by `aoc_main!()`.
+///
+/// Loads the input like [`input::load`], parses it once, runs every registered
+/// part of `day` on it and prints `Part N: answer` lines followed by the timing
+/// report, plus heap usage with the `alloc-stats` feature (see [`alloc`]). When
+/// the embedded input is used, answers are checked against the answers file in
+/// `input_dir` (see [`answers`]) and marked `OK` or `MISMATCH`.
+///
+/// The process exits with a failure status if the input is invalid, a part
+/// fails or an answer does not match.
+pub fn main(day: u8, embedded: &'static str, input_dir: &str) -> ExitCode {
+    let answers = if input::path_from_env().is_none() {
+        match Answers::load(&Path::new(input_dir).join(answers::FILE_NAME)) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+    let input = input::load(day, embedded);
+    let parts = day_parts(day);
+    if parts.is_empty() {
+        eprintln!("No parts registered for day {day}");
+        return ExitCode::FAILURE;
+    }
+
+    let ((result, alloc), total) = timing::timed(|| alloc::measure(|| run(day, &input, &parts)));
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Invalid input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut failed = false;
+    for part in &result.parts {
+        match &part.answer {
+            Ok(answer) => match answers.check(day, part.part.part, answer) {
+                Check::Unknown => println!("Part {}: {answer}", part.part.part),
+                check => {
+                    failed |= check != Check::Ok;
+                    println!("Part {}: {answer} {check}", part.part.part);
+                }
+            },
+            Err(e) => {
+                eprintln!("Part {}: {e}", part.part.part);
+                failed = true;
+            }
+        }
+    }
+
+    timing::report_with_alloc(Some(day), "total", &mut [total], alloc);
+    timing::report(Some(day), "parse", &mut [result.parse_time]);
+    for part in &result.parts {
+        let phase = format!("part{}", part.part.part);
+        timing::report(Some(day), &phase, &mut [part.time]);
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
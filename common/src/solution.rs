@@ -0,0 +1,27 @@
+use crate::error::AocError;
+use std::fmt::Display;
+
+/// The parse step of a puzzle solution, shared by its parts.
+///
+/// `#[aoc_parse]` implements this for a unit struct named after the day
+/// (`Day01`, `Day02`, ...), and `#[aoc_part(n)]` implements [`SolvePart<n>`]
+/// for it, so the runner, tests and benchmarks can drive any day the same way.
+pub trait Solution {
+    /// Day of the puzzle, starting from 1.
+    const DAY: u8;
+
+    /// Parsed input shared by the parts.
+    type Parsed: 'static;
+
+    /// Parses the raw input, reporting invalid input instead of panicking.
+    fn parse(input: &str) -> Result<Self::Parsed, AocError>;
+}
+
+/// Part `N` of a [`Solution`].
+pub trait SolvePart<const N: u8>: Solution {
+    type Answer: Display;
+
+    /// Solves the part. Parts may modify the parsed input, so run them in
+    /// order when they share it.
+    fn solve(parsed: &mut Self::Parsed) -> Result<Self::Answer, AocError>;
+}
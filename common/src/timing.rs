@@ -0,0 +1,309 @@
+use crate::alloc::AllocStats;
+use std::cell::RefCell;
+use std::env;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// Environment variable selecting the [`Output`] of timing reports.
+pub const ENV_VAR: &str = "AOC_TIMING";
+
+thread_local! {
+    static PHASES: RefCell<Vec<Phase>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Samples recorded for one named phase, such as `parse` or `part1`.
+#[derive(Debug, Clone)]
+pub struct Phase {
+    pub name: &'static str,
+    pub samples: Vec<Duration>,
+}
+
+/// Runs `f` and returns its value together with the elapsed time.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Runs `f` and records its duration under `name` for the current thread.
+///
+/// This is what `aoc_phase!` expands to. Recorded phases are printed by
+/// [`report_phases`], which `#[aoc_timed]` calls once the function returns.
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let (value, elapsed) = timed(f);
+    PHASES.with_borrow_mut(|phases| match phases.iter_mut().find(|p| p.name == name) {
+        Some(phase) => phase.samples.push(elapsed),
+        None => phases.push(Phase {
+            name,
+            samples: vec![elapsed],
+        }),
+    });
+    value
+}
+
+/// Removes and returns the phases recorded so far, in the order they first ran.
+pub fn take_phases() -> Vec<Phase> {
+    PHASES.take()
+}
+
+/// Reports the recorded phases of `day` and clears them.
+pub fn report_phases(day: Option<u8>) {
+    for mut phase in take_phases() {
+        report(day, phase.name, &mut phase.samples);
+    }
+}
+
+/// Where and how timing reports are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// Human-readable `[Duration]` and `[Phase]` lines on stdout (the default).
+    Stdout,
+    /// The same lines on stderr, keeping stdout for the answers.
+    Stderr,
+    /// One JSON object per line on stderr, e.g. `{"day":7,"phase":"part2","ns":1234}`.
+    Json,
+    /// No timing output.
+    Off,
+}
+
+impl Output {
+    /// Reads the mode from [`ENV_VAR`]: `stdout`, `stderr`, `json` or `off`.
+    pub fn from_env() -> Self {
+        match env::var(ENV_VAR).as_deref() {
+            Ok("stderr") => Self::Stderr,
+            Ok("json") => Self::Json,
+            Ok("off") => Self::Off,
+            _ => Self::Stdout,
+        }
+    }
+}
+
+/// Reports the samples of one phase in the [`Output`] selected by [`ENV_VAR`].
+///
+/// The phase `total` is the duration of a whole `#[aoc_timed]` function.
+pub fn report(day: Option<u8>, phase: &str, samples: &mut [Duration]) {
+    report_with_alloc(day, phase, samples, None);
+}
+
+/// Like [`report`], with the heap usage of the phase if it was measured (see
+/// [`crate::alloc`]).
+///
+/// Text output adds a `[Memory]` line, JSON output adds `peak_bytes` and
+/// `allocations` to the object of the phase, so there is one object per phase.
+pub fn report_with_alloc(
+    day: Option<u8>,
+    phase: &str,
+    samples: &mut [Duration],
+    alloc: Option<AllocStats>,
+) {
+    let output = Output::from_env();
+    match output {
+        Output::Off => {}
+        Output::Json => write_line(output, &json_line(day, phase, samples, alloc)),
+        Output::Stdout | Output::Stderr => {
+            write_line(output, &text_line(phase, samples));
+            if let Some(stats) = alloc {
+                write_line(output, &alloc_text_line(phase, stats));
+            }
+        }
+    }
+}
+
+fn write_line(output: Output, line: &str) {
+    // Reporting is best effort, a closed pipe must not abort the solution.
+    let _ = if output == Output::Stdout {
+        writeln!(io::stdout(), "{line}")
+    } else {
+        writeln!(io::stderr(), "{line}")
+    };
+}
+
+fn alloc_text_line(phase: &str, stats: AllocStats) -> String {
+    let label = if phase == "total" {
+        "[Memory]".to_string()
+    } else {
+        format!("[Memory] {phase}:")
+    };
+    format!(
+        "{label} peak {}, {} allocations",
+        format_bytes(stats.peak_bytes),
+        stats.allocations
+    )
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+fn text_line(phase: &str, samples: &mut [Duration]) -> String {
+    let label = if phase == "total" {
+        "[Duration]".to_string()
+    } else {
+        format!("[Phase] {phase}:")
+    };
+    match samples {
+        [elapsed] => format!("{label} {elapsed:.2?}"),
+        _ => format!("{label} {}", Stats::new(samples)),
+    }
+}
+
+fn json_line(
+    day: Option<u8>,
+    phase: &str,
+    samples: &mut [Duration],
+    alloc: Option<AllocStats>,
+) -> String {
+    let day = day.map_or_else(|| "null".to_string(), |d| d.to_string());
+    let timing = match samples {
+        [elapsed] => format!(r#""ns":{}"#, elapsed.as_nanos()),
+        _ => {
+            let stats = Stats::new(samples);
+            format!(
+                r#""runs":{},"ns":{},"min_ns":{},"mean_ns":{},"stddev_ns":{}"#,
+                stats.runs,
+                stats.median.as_nanos(),
+                stats.min.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.stddev.as_nanos()
+            )
+        }
+    };
+    let alloc = alloc.map_or_else(String::new, |stats| {
+        format!(
+            r#","peak_bytes":{},"allocations":{}"#,
+            stats.peak_bytes, stats.allocations
+        )
+    });
+    format!(r#"{{"day":{day},"phase":"{phase}",{timing}{alloc}}}"#)
+}
+
+/// Summary of repeated timing samples, as reported by `#[aoc_timed(runs = N)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, sorting them in place.
+    ///
+    /// # Panics
+    /// If `samples` is empty.
+    pub fn new(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "Cannot compute stats of zero samples");
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            runs: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?} ({} runs)",
+            self.min, self.median, self.mean, self.stddev, self.runs
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases() {
+        assert_eq!(phase("parse", || 1), 1);
+        phase("part1", || ());
+        phase("parse", || ());
+        let phases = take_phases();
+        let names: Vec<_> = phases.iter().map(|p| (p.name, p.samples.len())).collect();
+        assert_eq!(names, [("parse", 2), ("part1", 1)]);
+        assert!(take_phases().is_empty());
+    }
+
+    #[test]
+    fn test_report_lines() {
+        let mut one = [Duration::from_micros(1500)];
+        assert_eq!(text_line("total", &mut one), "[Duration] 1.50ms");
+        assert_eq!(text_line("parse", &mut one), "[Phase] parse: 1.50ms");
+        assert_eq!(
+            json_line(Some(7), "part2", &mut one, None),
+            r#"{"day":7,"phase":"part2","ns":1500000}"#
+        );
+
+        let mut many = [1, 3].map(Duration::from_nanos);
+        assert_eq!(
+            json_line(None, "total", &mut many, None),
+            r#"{"day":null,"phase":"total","runs":2,"ns":2,"min_ns":1,"mean_ns":2,"stddev_ns":1}"#
+        );
+    }
+
+    #[test]
+    fn test_alloc_lines() {
+        let stats = AllocStats {
+            allocations: 12,
+            peak_bytes: 1536,
+        };
+        assert_eq!(
+            alloc_text_line("total", stats),
+            "[Memory] peak 1.50 KiB, 12 allocations"
+        );
+        assert_eq!(
+            json_line(
+                Some(8),
+                "total",
+                &mut [Duration::from_nanos(5)],
+                Some(stats)
+            ),
+            r#"{"day":8,"phase":"total","ns":5,"peak_bytes":1536,"allocations":12}"#
+        );
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(3 << 20), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::new(&mut samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+}
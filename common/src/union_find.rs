@@ -0,0 +1,132 @@
+/// Disjoint-set forest over the elements `0..n`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// Size of the component, only meaningful for roots.
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Number of elements.
+    pub const fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    pub const fn count(&self) -> usize {
+        self.components
+    }
+
+    /// Representative of the set containing `i`.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Point the whole path at the root.
+        let mut node = i;
+        while self.parent[node] != root {
+            node = std::mem::replace(&mut self.parent[node], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `i` and `j`, returning `false` if they were already joined.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root_i, mut root_j) = (self.find(i), self.find(j));
+        if root_i == root_j {
+            return false;
+        }
+        if self.size[root_i] < self.size[root_j] {
+            std::mem::swap(&mut root_i, &mut root_j);
+        }
+        self.parent[root_j] = root_i;
+        self.size[root_i] += self.size[root_j];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    /// Size of the set containing `i`.
+    pub fn size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+
+    /// Sizes of all sets, in no particular order.
+    pub fn sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect()
+    }
+
+    /// Members of every set, each sorted, ordered by their smallest element.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_of_root = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for i in 0..self.len() {
+            let root = self.find(i);
+            if group_of_root[root] == usize::MAX {
+                group_of_root[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[group_of_root[root]].push(i);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count(), 6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(4, 1));
+        assert!(!uf.union(0, 4));
+        assert!(uf.union(2, 5));
+        assert_eq!(uf.count(), 3);
+        assert!(uf.connected(4, 0));
+        assert!(!uf.connected(4, 5));
+        assert_eq!(uf.size(1), 3);
+        assert_eq!(uf.size(3), 1);
+
+        let mut sizes = uf.sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 2, 3]);
+        assert_eq!(uf.groups(), [vec![0, 1, 4], vec![2, 5], vec![3]]);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // A recursive `find` would overflow the stack here.
+        let n = 1_000_000;
+        let mut uf = UnionFind::new(n);
+        for i in 1..n {
+            uf.parent[i - 1] = i;
+        }
+        assert_eq!(uf.find(0), n - 1);
+        assert_eq!(uf.parent[0], n - 1);
+    }
+}
@@ -0,0 +1,90 @@
+use common::error::AocError;
+use common::parse::ParseError;
+use macros::{aoc_parse, aoc_part, aoc_test};
+use std::str::FromStr;
+
+pub enum Instruction {
+    Left(i16),
+    Right(i16),
+}
+
+/// Parses `L68` or `R48`.
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut chars = s.chars();
+        let dir = chars.next();
+        let amount = chars.as_str();
+        let amount: i16 = amount
+            .parse()
+            .ok()
+            .filter(|&a| a >= 0)
+            .ok_or_else(|| ParseError::new(2, format!("invalid amount `{amount}`")))?;
+        match dir {
+            Some('L') => Ok(Self::Left(amount)),
+            Some('R') => Ok(Self::Right(amount)),
+            _ => Err(ParseError::new(1, format!("invalid direction in `{s}`"))),
+        }
+    }
+}
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, AocError> {
+    Ok(common::parse::lines(input)?)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(instructions: &[Instruction]) -> usize {
+    let mut current_pos = 50;
+    let mut zero_count = 0;
+
+    for instruction in instructions {
+        current_pos = match instruction {
+            Instruction::Left(amount) => current_pos - amount,
+            Instruction::Right(amount) => current_pos + amount,
+        }
+        .rem_euclid(100);
+
+        if current_pos == 0 {
+            zero_count += 1;
+        }
+    }
+
+    zero_count
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(instructions: &[Instruction]) -> i16 {
+    let mut current_pos: i16 = 50;
+    let mut zero_count = 0;
+
+    for instruction in instructions {
+        let (amount, is_left) = match instruction {
+            Instruction::Left(a) => (*a, true),
+            Instruction::Right(a) => (*a, false),
+        };
+
+        let first_hit = if is_left {
+            if current_pos > 0 { current_pos } else { 100 }
+        } else {
+            let dist = (100 - current_pos) % 100;
+            if dist == 0 { 100 } else { dist }
+        };
+
+        if amount >= first_hit {
+            zero_count += 1 + (amount - first_hit) / 100;
+        }
+
+        current_pos = if is_left {
+            current_pos - amount
+        } else {
+            current_pos + amount
+        }
+        .rem_euclid(100);
+    }
+
+    zero_count
+}
+
+aoc_test!(part1: 3, part2: 6);
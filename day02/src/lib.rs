@@ -0,0 +1,218 @@
+use common::error::AocError;
+use common::parse::ParseError;
+use macros::{AocParse, aoc_parse, aoc_part, aoc_test};
+
+#[derive(AocParse)]
+#[aoc(format = "{start}-{end}")]
+struct IdRange {
+    start: u64,
+    end: u64,
+}
+
+/// Parses the comma separated ranges of the single input line.
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Vec<(u64, u64)>, AocError> {
+    let mut column = 1;
+    let mut ranges = Vec::new();
+    for range in input.trim_end().split(',') {
+        let located = |e: ParseError| ParseError::new(column + e.column - 1, e.message).at_line(1);
+        let IdRange { start, end } = range.parse().map_err(located)?;
+        if start == 0 {
+            return Err(located(ParseError::new(1, "IDs start at 1")).into());
+        }
+        ranges.push((start, end));
+        column += range.len() + 1;
+    }
+    Ok(ranges)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(ranges: &[(u64, u64)]) -> u64 {
+    let mut total = 0;
+    for_each_digit_span(ranges, |digits, span| {
+        // Skip odd digit counts, they can never be split into two halves
+        if digits % 2 == 0 {
+            total += span.filter(|&i| is_invalid_part1(i)).sum::<u64>();
+        }
+    });
+    total
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(ranges: &[(u64, u64)]) -> u64 {
+    let mut total = 0;
+    for_each_digit_span(ranges, |_, span| {
+        total += span.filter(|&i| is_invalid_part2(i)).sum::<u64>();
+    });
+    total
+}
+
+/// Splits every range into sub-ranges sharing the same number of digits.
+fn for_each_digit_span(ranges: &[(u64, u64)], mut f: impl FnMut(u32, std::ops::Range<u64>)) {
+    for &(start, end) in ranges {
+        let mut n = start;
+        while n <= end {
+            let digits = n.ilog10() + 1;
+            let next_power_of_10 = 10u64.pow(digits);
+            let limit = std::cmp::min(end + 1, next_power_of_10);
+            f(digits, n..limit);
+            n = limit;
+        }
+    }
+}
+
+/// Returns `true` if `n` is some digit sequence repeated exactly twice.
+pub const fn is_invalid_part1(n: u64) -> bool {
+    let len = n.ilog10() + 1;
+    if !len.is_multiple_of(2) {
+        return false;
+    }
+
+    let mid = len / 2;
+    let divisor = 10u64.pow(mid);
+    let first_half = n / divisor;
+    let second_half = n % divisor;
+
+    first_half == second_half
+}
+
+/// Returns `true` if `n` is some digit sequence repeated at least twice.
+pub const fn is_invalid_part2(n: u64) -> bool {
+    let len = n.ilog10() + 1;
+
+    // Try all possible substring lengths from 1 up to len/2
+    let mut l = 1;
+    while l <= len / 2 {
+        if len.is_multiple_of(l) {
+            let repeats = len / l;
+            let prefix = n / 10u64.pow(len - l);
+
+            let mut multiplier = 0;
+            let shift = 10u64.pow(l);
+            let mut i = 0;
+            while i < repeats {
+                multiplier = multiplier * shift + 1;
+                i += 1;
+            }
+
+            if prefix * multiplier == n {
+                return true;
+            }
+        }
+        l += 1;
+    }
+    false
+}
+
+aoc_test!(part1: 1227775554, part2: 4174379265);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macros::aoc_input;
+
+    const EXAMPLE: &str = aoc_input!(example);
+
+    #[test]
+    fn test_is_invalid_part1() {
+        assert!(is_invalid_part1(11));
+        assert!(is_invalid_part1(22));
+        assert!(is_invalid_part1(99));
+        assert!(is_invalid_part1(1010));
+        assert!(is_invalid_part1(1188511885));
+        assert!(is_invalid_part1(222222));
+        assert!(is_invalid_part1(446446));
+        assert!(is_invalid_part1(38593859));
+
+        assert!(!is_invalid_part1(101));
+        assert!(!is_invalid_part1(123));
+        assert!(!is_invalid_part1(12345));
+    }
+
+    #[test]
+    fn test_is_invalid_part2() {
+        // Part 1 examples are also valid for Part 2 (repeated 2 times)
+        assert!(is_invalid_part2(11));
+        assert!(is_invalid_part2(22));
+
+        // New Part 2 examples
+        assert!(is_invalid_part2(12341234)); // 2 times
+        assert!(is_invalid_part2(123123123)); // 3 times
+        assert!(is_invalid_part2(1212121212)); // 5 times
+        assert!(is_invalid_part2(1111111)); // 7 times
+
+        // From range examples
+        assert!(is_invalid_part2(111));
+        assert!(is_invalid_part2(999));
+        assert!(is_invalid_part2(565656));
+        assert!(is_invalid_part2(824824824));
+        assert!(is_invalid_part2(2121212121));
+    }
+
+    #[test]
+    fn test_example_ranges() {
+        let ranges = parse_input(EXAMPLE).unwrap();
+
+        let mut invalid_ids_part1 = Vec::new();
+        let mut invalid_ids_part2 = Vec::new();
+
+        for (start, end) in ranges {
+            let mut n = start;
+            while n <= end {
+                let digits = n.ilog10() + 1;
+                let next_power_of_10 = 10u64.pow(digits);
+                let limit = std::cmp::min(end + 1, next_power_of_10);
+
+                if digits % 2 == 0 {
+                    for i in n..limit {
+                        if is_invalid_part1(i) {
+                            invalid_ids_part1.push(i);
+                        }
+                    }
+                }
+
+                for i in n..limit {
+                    if is_invalid_part2(i) {
+                        invalid_ids_part2.push(i);
+                    }
+                }
+                n = limit;
+            }
+        }
+
+        let expected_part1 = vec![
+            11, 22,         // 11-22
+            99,         // 95-115
+            1010,       // 998-1012
+            1188511885, // 1188511880-1188511890
+            222222,     // 222220-222224
+            // 1698522-1698528 none
+            446446,   // 446443-446449
+            38593859, // 38593856-38593862
+        ];
+
+        assert_eq!(invalid_ids_part1, expected_part1);
+
+        let sum_part1: u64 = invalid_ids_part1.iter().sum();
+        assert_eq!(sum_part1, 1227775554);
+
+        let expected_part2 = vec![
+            11, 22, // 11-22
+            99, 111, // 95-115
+            999, 1010,       // 998-1012
+            1188511885, // 1188511880-1188511890
+            222222,     // 222220-222224
+            // 1698522-1698528 none
+            446446,     // 446443-446449
+            38593859,   // 38593856-38593862
+            565656,     // 565653-565659
+            824824824,  // 824824821-824824827
+            2121212121, // 2121212118-2121212124
+        ];
+
+        assert_eq!(invalid_ids_part2, expected_part2);
+
+        let sum_part2: u64 = invalid_ids_part2.iter().sum();
+        assert_eq!(sum_part2, 4174379265);
+    }
+}
@@ -0,0 +1,98 @@
+use common::error::AocError;
+use common::parse::{self, ParseError};
+use macros::{aoc_parse, aoc_part, aoc_test};
+
+/// Digits turned on per bank in part 2, and so the fewest a bank can have.
+pub const PART2_DIGITS: usize = 12;
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, AocError> {
+    Ok(parse::lines_with(input, parse_digits)?)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(banks: &[Vec<u8>]) -> u64 {
+    banks.iter().map(|digits| solve_bank::<2>(digits)).sum()
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(banks: &[Vec<u8>]) -> u64 {
+    banks
+        .iter()
+        .map(|digits| solve_bank::<PART2_DIGITS>(digits))
+        .sum()
+}
+
+/// Parses a bank of at least [`PART2_DIGITS`] digits.
+pub fn parse_digits(line: &str) -> Result<Vec<u8>, ParseError> {
+    let digits: Vec<u8> = line
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| ParseError::new(i + 1, format!("expected a digit, found `{c}`")))
+        })
+        .collect::<Result<_, _>>()?;
+    if digits.len() < PART2_DIGITS {
+        return Err(ParseError::new(
+            digits.len() + 1,
+            format!(
+                "expected at least {PART2_DIGITS} digits, found {}",
+                digits.len()
+            ),
+        ));
+    }
+    Ok(digits)
+}
+
+/// Largest number formed by picking `K` digits of the bank in order.
+///
+/// # Panics
+/// If the bank has fewer than `K` digits.
+pub fn solve_bank<const K: usize>(digits: &[u8]) -> u64 {
+    let n = digits.len();
+    let mut result = 0;
+    let mut current_pos = 0;
+
+    for remaining_needed in (0..K).rev() {
+        // We can search up to a point where we still have enough digits left
+        // limit is inclusive index
+        let limit = n - 1 - remaining_needed;
+
+        let mut max_digit = 0;
+        let mut max_idx = current_pos;
+
+        let slice = &digits[current_pos..=limit];
+
+        for (idx, &digit) in slice.iter().enumerate() {
+            if digit > max_digit {
+                max_digit = digit;
+                max_idx = current_pos + idx;
+                if max_digit == 9 {
+                    break; // Optimization: can't get better than 9
+                }
+            }
+        }
+
+        result = result * 10 + max_digit as u64;
+        current_pos = max_idx + 1;
+    }
+
+    result
+}
+
+aoc_test!(part1: 357, part2: 3121910778619);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_bank() {
+        let err = parse_input("987654321111111\n123\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected at least 12 digits, found 3"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use common::error::AocError;
+use common::grid::Grid;
+use macros::{aoc_parse, aoc_part, aoc_test};
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Grid<bool>, AocError> {
+    let grid = Grid::parse(input, |c| match c {
+        '.' => Some(false),
+        '@' => Some(true),
+        _ => None,
+    })?;
+    Ok(grid)
+}
+
+/// Counts occupied cells among the 8 neighbours of `(x, y)`.
+pub fn count_neighbors(grid: &Grid<bool>, x: usize, y: usize) -> usize {
+    grid.neighbors8(x, y).filter(|&(x, y)| grid[(x, y)]).count()
+}
+
+/// Indices of rolls with fewer than 4 occupied neighbours.
+pub fn accessible_rolls(grid: &Grid<bool>) -> Vec<usize> {
+    grid.iter()
+        .filter(|&((x, y), &roll)| roll && count_neighbors(grid, x, y) < 4)
+        .map(|((x, y), _)| grid.index_of(x, y))
+        .collect()
+}
+
+#[aoc_part(1)]
+pub fn part1(grid: &Grid<bool>) -> usize {
+    accessible_rolls(grid).len()
+}
+
+#[aoc_part(2)]
+pub fn part2(grid: &mut Grid<bool>) -> usize {
+    let mut total_removed = 0;
+
+    loop {
+        let accessible = accessible_rolls(grid);
+        if accessible.is_empty() {
+            break;
+        }
+        total_removed += accessible.len();
+        for idx in accessible {
+            grid.cells_mut()[idx] = false;
+        }
+    }
+    total_removed
+}
+
+aoc_test!(part1: 13, part2: 43);
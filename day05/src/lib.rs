@@ -0,0 +1,70 @@
+use common::error::AocError;
+use common::parse::{AocParse, ParseError};
+use common::range_set::RangeSet;
+use macros::{AocParse, aoc_parse, aoc_part, aoc_test};
+
+#[derive(AocParse)]
+#[aoc(format = "{start}-{end}")]
+struct FreshRange {
+    start: u64,
+    end: u64,
+}
+
+#[derive(Clone)]
+pub struct Input {
+    pub ranges: RangeSet<u64>,
+    pub available: Vec<u64>,
+}
+
+impl Input {
+    /// Parses the fresh ranges, a blank line, then the available IDs.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut ranges = RangeSet::new();
+        let mut available = Vec::new();
+        let mut in_ranges = true;
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                // The first blank line after the ranges starts the IDs.
+                in_ranges = in_ranges && ranges.is_empty();
+                continue;
+            }
+            if in_ranges {
+                let FreshRange { start, end } =
+                    FreshRange::parse_line(line).map_err(|e| e.at_line(i + 1))?;
+                ranges.insert(start..=end);
+            } else {
+                let id = line.parse().map_err(|e| {
+                    ParseError::new(1, format!("invalid ID `{line}`: {e}")).at_line(i + 1)
+                })?;
+                available.push(id);
+            }
+        }
+        if in_ranges {
+            return Err(AocError::new("missing blank line before the available IDs"));
+        }
+        Ok(Self { ranges, available })
+    }
+}
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Input, AocError> {
+    Input::parse(input)
+}
+
+#[aoc_part(1)]
+pub fn part1(input: &Input) -> usize {
+    input
+        .available
+        .iter()
+        .filter(|&&id| input.ranges.contains(id))
+        .count()
+}
+
+#[aoc_part(2)]
+pub fn part2(input: &Input) -> u64 {
+    // Overlapping and adjacent ranges are merged, so every fresh ID is counted once.
+    input.ranges.len() as u64
+}
+
+aoc_test!(part1: 3, part2: 14);
@@ -0,0 +1,188 @@
+use common::error::AocError;
+use macros::{aoc_parse, aoc_part, aoc_test};
+use std::ops::Range;
+
+/// Number lines, the operator of each problem and the columns of each problem.
+pub type Worksheet = (Vec<String>, Vec<Operator>, Vec<Range<usize>>);
+
+#[aoc_part(1)]
+pub fn solve_part1((lines, operators, ranges): &Worksheet) -> u64 {
+    solve_problems(parse_part1_problems(lines, operators, ranges))
+}
+
+#[aoc_part(2)]
+pub fn solve_part2((lines, operators, ranges): &Worksheet) -> u64 {
+    solve_problems(parse_part2_problems(lines, operators, ranges))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    pub const fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            b'+' => Some(Self::Add),
+            b'*' => Some(Self::Multiply),
+            _ => None,
+        }
+    }
+}
+
+pub struct Problem {
+    pub numbers: Vec<u64>,
+    pub operator: Operator,
+}
+
+impl Problem {
+    pub fn solve(self) -> u64 {
+        let num_iter = self.numbers.into_iter();
+        match self.operator {
+            Operator::Add => num_iter.sum(),
+            Operator::Multiply => num_iter.product(),
+        }
+    }
+}
+
+#[aoc_parse]
+pub fn parse_raw_input(input: &str) -> Result<Worksheet, AocError> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let Some(operator_line) = lines.pop() else {
+        return Ok((vec![], vec![], vec![]));
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if let Some((col, c)) = line
+            .char_indices()
+            .find(|&(_, c)| c != ' ' && !c.is_ascii_digit())
+        {
+            return Err(AocError::new(format!("expected a digit, found `{c}`"))
+                .at_line(i + 1)
+                .at_column(col + 1));
+        }
+    }
+
+    let max_len = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let mut ranges = Vec::new();
+    let mut start_col = None;
+
+    for col in 0..max_len {
+        let mut is_empty_col = true;
+
+        for line in &lines {
+            let bytes = line.as_bytes();
+            if col < bytes.len() && bytes[col] != b' ' {
+                is_empty_col = false;
+                break;
+            }
+        }
+        if !is_empty_col {
+            if start_col.is_none() {
+                start_col = Some(col);
+            }
+        } else if let Some(start) = start_col {
+            ranges.push(start..col);
+            start_col = None;
+        }
+    }
+
+    if let Some(start) = start_col {
+        ranges.push(start..max_len);
+    }
+
+    let operators = ranges
+        .iter()
+        .map(|range| {
+            parse_operator(operator_line, range.clone()).ok_or_else(|| {
+                AocError::new("missing operator")
+                    .at_line(lines.len() + 1)
+                    .at_column(range.start + 1)
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let lines = lines.into_iter().map(str::to_string).collect();
+    Ok((lines, operators, ranges))
+}
+
+fn parse_operator(last_line: &str, mut range: Range<usize>) -> Option<Operator> {
+    let bytes = last_line.as_bytes();
+    range.find_map(|col| {
+        if col < bytes.len() {
+            Operator::from_byte(bytes[col])
+        } else {
+            None
+        }
+    })
+}
+
+pub fn parse_part1_problems(
+    lines: &[String],
+    operators: &[Operator],
+    ranges: &[Range<usize>],
+) -> impl Iterator<Item = Problem> {
+    ranges.iter().zip(operators).map(|(range, &operator)| {
+        let mut numbers = Vec::new();
+
+        // Parse numbers from all lines
+        for line in lines.iter() {
+            let slice = if range.end <= line.len() {
+                &line[range.clone()]
+            } else if range.start < line.len() {
+                &line[range.start..]
+            } else {
+                ""
+            };
+
+            let trimmed = slice.trim();
+            if let Ok(num) = trimmed.parse::<u64>() {
+                numbers.push(num);
+            }
+        }
+
+        Problem { numbers, operator }
+    })
+}
+
+pub fn parse_part2_problems(
+    lines: &[String],
+    operators: &[Operator],
+    ranges: &[Range<usize>],
+) -> impl Iterator<Item = Problem> {
+    ranges.iter().zip(operators).map(|(range, &operator)| {
+        let mut numbers = Vec::new();
+
+        let height = lines.len();
+        // Iterate columns from right to left within the range
+        for col in (range.start..range.end).rev() {
+            let mut num_val: u64 = 0;
+            let mut has_digits = false;
+
+            // Collect digits from top rows (0 to height-2)
+            for line in lines.iter().take(height) {
+                let bytes = line.as_bytes();
+                if col < bytes.len() {
+                    let b = bytes[col];
+                    if b.is_ascii_digit() {
+                        num_val = num_val * 10 + (b - b'0') as u64;
+                        has_digits = true;
+                    }
+                }
+            }
+
+            if has_digits {
+                numbers.push(num_val);
+            }
+        }
+
+        Problem { numbers, operator }
+    })
+}
+
+pub fn solve_problems(problems: impl Iterator<Item = Problem>) -> u64 {
+    problems.map(Problem::solve).sum()
+}
+
+aoc_test!(part1: 4277556, part2: 3263827);
@@ -0,0 +1,88 @@
+use common::error::AocError;
+use common::grid::Grid;
+use macros::{aoc_parse, aoc_part, aoc_test};
+
+#[aoc_parse]
+pub fn parse_input(content: &str) -> Result<Grid<u8>, AocError> {
+    Ok(Grid::parse(content, |c| u8::try_from(c).ok())?)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(grid: &Grid<u8>) -> u64 {
+    solve(grid, false)
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(grid: &Grid<u8>) -> u64 {
+    solve(grid, true)
+}
+
+pub fn solve(grid: &Grid<u8>, part2: bool) -> u64 {
+    let rows = grid.height();
+    if rows == 0 {
+        return 0;
+    }
+    let cols = grid.width();
+
+    // Use Vec instead of HashMap for performance (dense grid, small keys)
+    let mut beams = vec![0u64; cols];
+    let mut next_beams = vec![0u64; cols];
+    let mut split_count = 0;
+
+    // Find 'S'
+    if let Some(c) = grid.row(0).iter().position(|&x| x == b'S') {
+        beams[c] = 1;
+    }
+
+    for r in 0..rows - 1 {
+        // Part 1: deduplicate beams (merge timelines)
+        if !part2 {
+            for count in &mut beams {
+                if *count > 0 {
+                    *count = 1;
+                }
+            }
+        }
+
+        // Reset next_beams buffer
+        next_beams.fill(0);
+
+        // Iterate over columns to update beams
+        let next_row = grid.row(r + 1);
+        for c in 0..cols {
+            let count = beams[c];
+            if count == 0 {
+                continue;
+            }
+
+            match next_row[c] {
+                b'^' => {
+                    if !part2 {
+                        split_count += 1;
+                    }
+                    if c > 0 {
+                        next_beams[c - 1] += count;
+                    }
+                    if c + 1 < cols {
+                        next_beams[c + 1] += count;
+                    }
+                }
+                _ => {
+                    // '.' or 'S' or anything else passes through
+                    next_beams[c] += count;
+                }
+            }
+        }
+
+        // Swap buffers for next iteration
+        std::mem::swap(&mut beams, &mut next_beams);
+    }
+
+    if part2 {
+        beams.iter().sum()
+    } else {
+        split_count
+    }
+}
+
+aoc_test!(part1: 21, part2: 40);
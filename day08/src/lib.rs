@@ -0,0 +1,116 @@
+use common::error::AocError;
+use common::point::Point3;
+use common::union_find::UnionFind;
+use macros::{aoc_parse, aoc_part, aoc_test};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::cmp::Reverse;
+
+/// Number of closest pairs connected in part 1.
+pub const CONNECTIONS: usize = 1000;
+
+pub type Pt = Point3<i64>;
+
+#[aoc_parse]
+pub fn parse_input(content: &str) -> Result<Vec<Pt>, AocError> {
+    Ok(common::parse::lines(content)?)
+}
+
+#[derive(Clone, Copy)]
+struct Pair {
+    u: usize,
+    v: usize,
+    dist_sq: i64,
+}
+
+#[cfg(feature = "rayon")]
+fn get_sorted_pairs(points: &[Pt]) -> Vec<Pair> {
+    let mut pairs: Vec<Pair> = (0..points.len())
+        .into_par_iter()
+        .flat_map(|i| {
+            (i + 1..points.len()).into_par_iter().map(move |j| Pair {
+                u: i,
+                v: j,
+                dist_sq: points[i].euclidean_sq(&points[j]),
+            })
+        })
+        .collect();
+
+    pairs.par_sort_unstable_by_key(|p| p.dist_sq);
+    pairs
+}
+
+#[cfg(not(feature = "rayon"))]
+fn get_sorted_pairs(points: &[Pt]) -> Vec<Pair> {
+    let mut pairs = Vec::with_capacity(points.len() * points.len().saturating_sub(1) / 2);
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            pairs.push(Pair {
+                u: i,
+                v: j,
+                dist_sq: points[i].euclidean_sq(&points[j]),
+            });
+        }
+    }
+    pairs.sort_unstable_by_key(|p| p.dist_sq);
+    pairs
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(points: &[Pt]) -> usize {
+    largest_circuits_product(points, CONNECTIONS)
+}
+
+/// Connects the `connections` closest pairs and multiplies the sizes of the three largest circuits.
+pub fn largest_circuits_product(points: &[Pt], connections: usize) -> usize {
+    let pairs = get_sorted_pairs(points);
+    let mut uf = UnionFind::new(points.len());
+
+    for pair in pairs.iter().take(connections.min(pairs.len())) {
+        uf.union(pair.u, pair.v);
+    }
+
+    let mut sizes = uf.sizes();
+    sizes.sort_by_key(|&s| Reverse(s));
+
+    if sizes.len() < 3 {
+        sizes.iter().product()
+    } else {
+        sizes[0] * sizes[1] * sizes[2]
+    }
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(points: &[Pt]) -> i64 {
+    let pairs = get_sorted_pairs(points);
+    let mut uf = UnionFind::new(points.len());
+
+    for pair in pairs {
+        if uf.union(pair.u, pair.v) && uf.count() == 1 {
+            return points[pair.u].x() * points[pair.v].x();
+        }
+    }
+    0
+}
+
+aoc_test!(part2: 25272);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macros::aoc_input;
+
+    const EXAMPLE: &str = aoc_input!(example);
+
+    #[test]
+    fn test_example_part1() {
+        let points = parse_input(EXAMPLE).unwrap();
+        assert_eq!(largest_circuits_product(&points, 10), 40);
+    }
+
+    #[test]
+    fn test_no_points() {
+        assert!(get_sorted_pairs(&[]).is_empty());
+        assert_eq!(solve_part2(&[]), 0);
+    }
+}
@@ -0,0 +1,46 @@
+use common::error::AocError;
+use common::point::Point2;
+use common::polygon::RectilinearPolygon;
+use macros::{aoc_parse, aoc_part, aoc_test};
+
+pub type Point = Point2<i64>;
+
+/// Parses the red tiles, which must form a loop of horizontal and vertical lines.
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<RectilinearPolygon, AocError> {
+    RectilinearPolygon::new(common::parse::lines(input)?)
+}
+
+/// Number of tiles of the rectangle with opposite corners `a` and `b`.
+const fn tiles(a: Point, b: Point) -> u64 {
+    (a.x().abs_diff(b.x()) + 1) * (a.y().abs_diff(b.y()) + 1)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(poly: &RectilinearPolygon) -> u64 {
+    let coords = poly.vertices();
+    let mut max_area = 0;
+    for (i, &p1) in coords.iter().enumerate() {
+        for &p2 in &coords[i + 1..] {
+            max_area = max_area.max(tiles(p1, p2));
+        }
+    }
+    max_area
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(poly: &RectilinearPolygon) -> u64 {
+    let coords = poly.vertices();
+    let mut max_area = 0;
+    for (i, &p1) in coords.iter().enumerate() {
+        for &p2 in &coords[i + 1..] {
+            let area = tiles(p1, p2);
+            if area > max_area && poly.contains_rect(p1, p2) {
+                max_area = area;
+            }
+        }
+    }
+    max_area
+}
+
+aoc_test!(part1: 50, part2: 24);
@@ -0,0 +1,260 @@
+use common::error::AocError;
+use common::matrix::{Echelon, Matrix, Scalar};
+use common::parse::{self, ParseError, Scanner};
+use macros::{aoc_parse, aoc_part, aoc_test};
+use std::str::FromStr;
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Vec<Machine>, AocError> {
+    Ok(parse::lines_with(input, parse_line)?)
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(machines: &[Machine]) -> Result<usize, AocError> {
+    let mut total_presses = 0;
+    for (line_idx, machine) in machines.iter().enumerate() {
+        // Part 1: linear system over GF(2)
+        let buttons = machine.buttons_matrix(machine.lights.len());
+        total_presses += solve_part1_gf2(&machine.lights, &buttons).ok_or_else(|| {
+            AocError::new("no button presses turn on these lights").at_line(line_idx + 1)
+        })?;
+    }
+    Ok(total_presses)
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(machines: &[Machine]) -> Result<i64, AocError> {
+    let mut total_presses = 0;
+    for (line_idx, machine) in machines.iter().enumerate() {
+        // Part 2: ILP
+        let buttons = machine.buttons_matrix(machine.joltage.len());
+        total_presses += solve_part2_ilp(&machine.joltage, &buttons).ok_or_else(|| {
+            AocError::new("no button presses reach these joltage levels").at_line(line_idx + 1)
+        })?;
+    }
+    Ok(total_presses)
+}
+
+pub struct Machine {
+    pub lights: Vec<bool>,
+    pub button_indices: Vec<Vec<usize>>,
+    pub joltage: Vec<i64>,
+}
+
+impl Machine {
+    /// One row per light (or joltage counter) and one column per button, set
+    /// where the button affects the light.
+    ///
+    /// # Panics
+    /// If a button affects a light at `size` or beyond, which [`parse_line`] rejects.
+    pub fn buttons_matrix<T: Scalar + Default>(&self, size: usize) -> Matrix<T> {
+        let mut matrix = Matrix::new(size, self.button_indices.len());
+        for (btn_idx, indices) in self.button_indices.iter().enumerate() {
+            for &idx in indices {
+                matrix.set(idx, btn_idx, T::ONE);
+            }
+        }
+        matrix
+    }
+}
+
+/// Indicator lights written as `.##.`, `#` meaning on.
+struct Lights(Vec<bool>);
+
+impl FromStr for Lights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        s.chars()
+            .map(|c| match c {
+                '.' => Ok(false),
+                '#' => Ok(true),
+                _ => Err(format!("unexpected light `{c}`")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Parses `[lights] (button1) (button2) ... {joltage}`.
+pub fn parse_line(line: &str) -> Result<Machine, ParseError> {
+    let mut scanner = Scanner::new(line);
+    scanner.literal("[")?;
+    let Lights(lights) = scanner.field("lights", Some("]"))?;
+    scanner.literal("] ")?;
+
+    let mut button_indices = Vec::new();
+    let mut button_columns = Vec::new();
+    while scanner.eat("(") {
+        button_columns.push(scanner.column());
+        button_indices.push(scanner.list("button", Some(")"), ",")?);
+        scanner.literal(") ")?;
+    }
+
+    scanner.literal("{")?;
+    let joltage: Vec<i64> = scanner.list("joltage", Some("}"), ",")?;
+    scanner.literal("}")?;
+    scanner.finish()?;
+
+    // Every button must affect a light and its joltage counter.
+    for (indices, &column) in button_indices.iter().zip(&button_columns) {
+        if let Some(&idx) = indices
+            .iter()
+            .find(|&&idx| idx >= lights.len().min(joltage.len()))
+        {
+            return Err(ParseError::new(
+                column,
+                format!(
+                    "button index {idx} out of range (lights: {}, joltage counters: {})",
+                    lights.len(),
+                    joltage.len()
+                ),
+            ));
+        }
+    }
+
+    Ok(Machine {
+        lights,
+        button_indices,
+        joltage,
+    })
+}
+
+/// Fewest presses turning on exactly the `target` lights.
+///
+/// Pressing a button twice undoes it, so this is the solution of
+/// `buttons * x = target` over GF(2) with the fewest ones. `None` if there is
+/// none, or too many to enumerate (a nullspace of 64 or more vectors).
+pub fn solve_part1_gf2(target: &[bool], buttons: &Matrix<bool>) -> Option<usize> {
+    let mut system = buttons.augment(target);
+    let echelon = system.eliminate(buttons.cols());
+    if !echelon.consistent {
+        return None;
+    }
+    let mut particular = vec![false; buttons.cols()];
+    system.back_substitute(&echelon, &mut particular);
+
+    // Every solution is the particular one plus a combination of the nullspace basis.
+    let basis = system.nullspace(&echelon);
+    let combinations = u32::try_from(basis.len())
+        .ok()
+        .and_then(|n| 1usize.checked_shl(n))?;
+    (0..combinations)
+        .map(|mask| {
+            let mut x = particular.clone();
+            for (i, v) in basis.iter().enumerate() {
+                if (mask >> i) & 1 == 1 {
+                    x.iter_mut().zip(v).for_each(|(xi, &vi)| *xi ^= vi);
+                }
+            }
+            x.iter().filter(|&&pressed| pressed).count()
+        })
+        .min()
+}
+
+// Part 2: ILP Solver (Gaussian + Search)
+pub fn solve_part2_ilp(target: &[i64], buttons: &Matrix<i128>) -> Option<i64> {
+    let target: Vec<i128> = target.iter().map(|&t| t as i128).collect();
+    let mut system = buttons.augment(&target);
+    let echelon = system.eliminate(buttons.cols());
+    if !echelon.consistent {
+        return None;
+    }
+
+    let mut min_total = None;
+    let mut x = vec![0i128; buttons.cols()];
+    search_int(0, &system, &echelon, &mut x, &mut min_total);
+    min_total
+}
+
+/// Tries values for the free variables from `idx` on, keeping the smallest valid total.
+fn search_int(
+    idx: usize,
+    system: &Matrix<i128>,
+    echelon: &Echelon,
+    x: &mut [i128],
+    min_total: &mut Option<i64>,
+) {
+    let Some(&free) = echelon.free_cols.get(idx) else {
+        // Pivot variables follow from the free ones and must be non-negative integers.
+        if system.back_substitute_while(echelon, x, |v| v >= 0) {
+            let sum: i128 = x.iter().sum();
+            if let Ok(sum) = i64::try_from(sum)
+                && min_total.is_none_or(|m| sum < m)
+            {
+                *min_total = Some(sum);
+            }
+        }
+        return;
+    };
+
+    // Search range logic
+    let limit = if echelon.free_cols.len() > 1 {
+        200
+    } else {
+        20000
+    };
+    // Every variable is non-negative, so the free ones set so far bound the total.
+    let assigned: i128 = echelon.free_cols[..idx].iter().map(|&col| x[col]).sum();
+    for v in 0..=limit {
+        if min_total.is_some_and(|m| assigned + v >= i128::from(m)) {
+            break;
+        }
+        x[free] = v;
+        search_int(idx + 1, system, echelon, x, min_total);
+    }
+}
+
+aoc_test!(part1: 7, part2: 33);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Buttons (3) (1,3) (2) (2,3) (0,2) (0,1) as columns, one row per light.
+    const BUTTONS: [[i128; 6]; 4] = [
+        [0, 0, 0, 0, 1, 1],
+        [0, 1, 0, 0, 0, 1],
+        [0, 0, 1, 1, 1, 0],
+        [1, 1, 0, 1, 0, 0],
+    ];
+
+    #[test]
+    fn test_part1_example_1() {
+        let target = [false, true, true, false];
+        let buttons = Matrix::from_rows(BUTTONS.map(|row| row.map(|b| b == 1)));
+
+        assert_eq!(solve_part1_gf2(&target, &buttons), Some(2));
+
+        // Buttons that do nothing leave a nullspace too large to enumerate.
+        assert_eq!(solve_part1_gf2(&[false], &Matrix::new(1, 64)), None);
+    }
+
+    #[test]
+    fn test_invalid_machines() {
+        let err = parse_input("[.#] (1) {3,4}\n[.#] (0) (5) {3,4}\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 11: button index 5 out of range (lights: 2, joltage counters: 2)"
+        );
+
+        // The only button turns on both lights together.
+        let machines = parse_input("[.#] (0,1) {3,4}\n").unwrap();
+        let err = solve_part1(&machines).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: no button presses turn on these lights"
+        );
+        assert!(solve_part2(&machines).is_err());
+    }
+
+    #[test]
+    fn test_part2_example_1() {
+        let target = vec![3, 5, 4, 7];
+        let buttons = Matrix::from_rows(BUTTONS);
+
+        assert_eq!(solve_part2_ilp(&target, &buttons), Some(10));
+    }
+}
@@ -0,0 +1,74 @@
+use common::error::AocError;
+use common::graph::Graph;
+use common::parse::AocParse;
+use macros::{AocParse, aoc_parse, aoc_part, aoc_test};
+
+#[derive(AocParse)]
+#[aoc(format = "{src}: {dests}")]
+struct Connections {
+    src: String,
+    #[aoc(sep = " ")]
+    dests: Vec<String>,
+}
+
+/// Parses the devices and their outputs.
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Graph, AocError> {
+    let connections = Connections::parse_lines(input)?;
+    Ok(Graph::from_adjacency(connections.iter().map(|c| {
+        (c.src.as_str(), c.dests.iter().map(String::as_str))
+    })))
+}
+
+/// Counts distinct paths from device `from` to device `to`, 0 if either is missing.
+///
+/// Fails if a cycle lies on one of the paths, as there are infinitely many.
+pub fn count_paths(graph: &Graph, from: &str, to: &str) -> Result<u64, AocError> {
+    let (Some(from_id), Some(to_id)) = (graph.id(from), graph.id(to)) else {
+        return Ok(0);
+    };
+    graph.count_paths(from_id, to_id).ok_or_else(|| {
+        AocError::new(format!(
+            "infinitely many paths from `{from}` to `{to}`, a cycle lies between them"
+        ))
+    })
+}
+
+#[aoc_part(1)]
+pub fn solve_part1(graph: &Graph) -> Result<u64, AocError> {
+    count_paths(graph, "you", "out")
+}
+
+#[aoc_part(2)]
+pub fn solve_part2(graph: &Graph) -> Result<u64, AocError> {
+    // Check path: svr -> dac -> fft -> out
+    let route_a = count_paths(graph, "svr", "dac")?
+        * count_paths(graph, "dac", "fft")?
+        * count_paths(graph, "fft", "out")?;
+
+    // Check path: svr -> fft -> dac -> out
+    let route_b = count_paths(graph, "svr", "fft")?
+        * count_paths(graph, "fft", "dac")?
+        * count_paths(graph, "dac", "out")?;
+
+    Ok(route_a + route_b)
+}
+
+aoc_test!(part1: 5);
+aoc_test!(example = 2, part2: 2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycles() {
+        // `aaa <-> bbb` is reachable from `you` but cannot reach `out`.
+        let graph = parse_input("you: aaa out\naaa: bbb\nbbb: aaa\n").unwrap();
+        assert_eq!(solve_part1(&graph), Ok(1));
+
+        let graph = parse_input("you: aaa\naaa: bbb out\nbbb: aaa\n").unwrap();
+        let err = solve_part1(&graph).unwrap_err();
+        assert!(err.reason.contains("infinitely many paths"), "{err}");
+    }
+}
@@ -0,0 +1,366 @@
+use common::bit_grid::BitGrid;
+use common::error::AocError;
+use common::exact_cover::ExactCover;
+use common::grid::Grid;
+use common::parse::AocParse;
+use common::point::Point2;
+use macros::{AocParse, aoc_parse, aoc_part, aoc_test};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape {
+    pub id: usize,
+    pub points: Vec<Point>,
+    /// Cells of each row as a bit mask, column `x` as bit `x`.
+    pub rows: Vec<u64>,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Cell of a shape, `x` is the column and `y` the row.
+pub type Point = Point2<i32>;
+
+impl Shape {
+    pub fn normalize(&mut self) {
+        if self.points.is_empty() {
+            return;
+        }
+        let min_x = self.points.iter().map(|p| p.x()).min().unwrap();
+        let min_y = self.points.iter().map(|p| p.y()).min().unwrap();
+        let offset = Point::new(min_x, min_y);
+        for p in &mut self.points {
+            *p -= offset;
+        }
+        // Recalculate dims
+        self.height = (self.points.iter().map(|p| p.y()).max().unwrap() + 1) as usize;
+        self.width = (self.points.iter().map(|p| p.x()).max().unwrap() + 1) as usize;
+
+        // Sort points row by row for consistent comparison
+        self.points.sort_by_key(|p| (p.y(), p.x()));
+
+        self.rows = vec![0; self.height];
+        for p in &self.points {
+            self.rows[p.y() as usize] |= 1 << p.x();
+        }
+    }
+
+    /// Whether the shape fits on `grid` with its top-left corner at `(x, y)`.
+    pub fn fits(&self, grid: &BitGrid, x: usize, y: usize) -> bool {
+        self.rows
+            .iter()
+            .enumerate()
+            .all(|(dy, &mask)| grid.fits(x, y + dy, mask))
+    }
+
+    pub fn place(&self, grid: &mut BitGrid, x: usize, y: usize) {
+        for (dy, &mask) in self.rows.iter().enumerate() {
+            grid.insert(x, y + dy, mask);
+        }
+    }
+
+    pub fn rotate(&self) -> Shape {
+        let mut new_points = Vec::new();
+        for p in &self.points {
+            new_points.push(p.rotate_cw());
+        }
+        let mut s = Shape {
+            id: self.id,
+            points: new_points,
+            rows: Vec::new(),
+            height: 0,
+            width: 0,
+        };
+        s.normalize();
+        s
+    }
+
+    pub fn flip(&self) -> Shape {
+        let mut new_points = Vec::new();
+        for p in &self.points {
+            new_points.push(p.flip_x());
+        }
+        let mut s = Shape {
+            id: self.id,
+            points: new_points,
+            rows: Vec::new(),
+            height: 0,
+            width: 0,
+        };
+        s.normalize();
+        s
+    }
+}
+
+// Generate all 8 orientations (or fewer if symmetric)
+pub fn generate_orientations(base: &Shape) -> Vec<Shape> {
+    let mut distinct = HashSet::new();
+    let mut results = Vec::new();
+
+    let mut curr = base.clone();
+    // 4 rotations
+    for _ in 0..4 {
+        let key = curr.points.clone();
+        if distinct.insert(key) {
+            results.push(curr.clone());
+        }
+
+        // Flip
+        let flipped = curr.flip();
+        let key_flip = flipped.points.clone();
+        if distinct.insert(key_flip) {
+            results.push(flipped);
+        }
+
+        curr = curr.rotate();
+    }
+    results
+}
+
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub requirements: Vec<usize>, // List of shape IDs to place
+}
+
+/// Orientations of every shape by ID, and the regions to fill.
+pub type Puzzle = (HashMap<usize, Vec<Shape>>, Vec<Region>);
+
+#[derive(AocParse)]
+#[aoc(format = "{width}x{height}: {counts}")]
+struct RegionLine {
+    width: usize,
+    height: usize,
+    #[aoc(sep = " ")]
+    counts: Vec<usize>,
+}
+
+#[aoc_parse]
+pub fn parse_input(input: &str) -> Result<Puzzle, AocError> {
+    let mut shapes_map = HashMap::new();
+    let mut regions = Vec::new();
+    // Line of each region, to report shapes that are never defined.
+    let mut region_lines = Vec::new();
+
+    // 1-based line numbers for error messages.
+    let mut lines = (1..).zip(input.lines()).peekable();
+
+    while let Some(&(line_no, line)) = lines.peek() {
+        let line = line.trim();
+        if line.is_empty() {
+            lines.next();
+            continue;
+        }
+
+        // Region line: "WxH: counts"
+        if line.contains('x') && line.contains(':') {
+            lines.next();
+            let RegionLine {
+                width,
+                height,
+                counts,
+            } = RegionLine::parse_line(line).map_err(|e| e.at_line(line_no))?;
+
+            let mut requirements = Vec::new();
+            for (id, &cnt) in counts.iter().enumerate() {
+                for _ in 0..cnt {
+                    requirements.push(id);
+                }
+            }
+            regions.push(Region {
+                width,
+                height,
+                requirements,
+            });
+            region_lines.push(line_no);
+            continue;
+        }
+
+        // Shape header: "ID:"
+        let id = line
+            .strip_suffix(':')
+            .and_then(|id| id.parse::<usize>().ok())
+            .ok_or_else(|| AocError::new(format!("unexpected line `{line}`")).at_line(line_no))?;
+        lines.next(); // Consume header
+        let mut block = Vec::new();
+        while let Some(&(_, shape_line)) = lines.peek() {
+            if shape_line.trim().is_empty() || shape_line.contains(':') {
+                break;
+            }
+            block.push(shape_line);
+            lines.next();
+        }
+
+        let grid = Grid::from_lines(block, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .map_err(|e| {
+            // Lines of the block are counted from the one after the header.
+            let line = e.line.map(|l| l + line_no);
+            AocError {
+                line,
+                ..AocError::from(e)
+            }
+        })?;
+        // Rotated shapes turn columns into rows, and each row is a `u64` mask.
+        if grid.width().max(grid.height()) > 64 {
+            return Err(
+                AocError::new("shapes larger than 64 cells across are not supported")
+                    .at_line(line_no),
+            );
+        }
+        let points = grid
+            .iter()
+            .filter(|&(_, &filled)| filled)
+            .map(|((x, y), _)| Point::new(x as i32, y as i32))
+            .collect();
+
+        let mut shape = Shape {
+            id,
+            points,
+            rows: Vec::new(),
+            width: 0,
+            height: 0,
+        };
+        shape.normalize();
+        shapes_map.insert(id, generate_orientations(&shape));
+    }
+
+    for (region, &line_no) in regions.iter().zip(&region_lines) {
+        if let Some(id) = region
+            .requirements
+            .iter()
+            .find(|id| !shapes_map.contains_key(id))
+        {
+            return Err(AocError::new(format!("no shape {id}")).at_line(line_no));
+        }
+    }
+
+    Ok((shapes_map, regions))
+}
+
+#[aoc_part(1)]
+pub fn solve_part1((shapes, regions): &Puzzle) -> usize {
+    solve(shapes, regions)
+}
+
+pub fn solve(shapes_map: &HashMap<usize, Vec<Shape>>, regions: &[Region]) -> usize {
+    regions
+        .iter()
+        .filter(|region| fits(shapes_map, region))
+        .count()
+}
+
+/// Places the required shapes one by one on an empty grid, each at the first
+/// position in reading order where one of its orientations fits.
+///
+/// Finds a packing of most regions with room to spare without searching.
+fn first_fit(shapes_map: &HashMap<usize, Vec<Shape>>, region: &Region) -> bool {
+    let mut grid = BitGrid::new(region.width, region.height);
+    region.requirements.iter().all(|id| {
+        let placement = (0..region.height)
+            .flat_map(|y| (0..region.width).map(move |x| (x, y)))
+            .find_map(|(x, y)| {
+                let variant = shapes_map[id].iter().find(|v| v.fits(&grid, x, y))?;
+                Some((variant, x, y))
+            });
+        placement
+            .map(|(variant, x, y)| variant.place(&mut grid, x, y))
+            .is_some()
+    })
+}
+
+/// Whether all the required shapes can be packed into the region.
+///
+/// Tries [`first_fit`] first, then solves an exact cover problem with a primary
+/// column per shape, covered once for every copy of it, and a secondary column
+/// per cell, which may stay empty.
+fn fits(shapes_map: &HashMap<usize, Vec<Shape>>, region: &Region) -> bool {
+    let area: usize = region
+        .requirements
+        .iter()
+        .map(|id| shapes_map[id][0].points.len())
+        .sum();
+    if area > region.width * region.height {
+        return false;
+    }
+
+    // Enough room to give every piece its own bounding box.
+    let base = |id| &shapes_map[id][0];
+    let box_width = region.requirements.iter().map(|id| base(id).width);
+    let box_height = region.requirements.iter().map(|id| base(id).height);
+    let box_width = box_width.max().unwrap_or(0).max(1);
+    let box_height = box_height.max().unwrap_or(0).max(1);
+    if (region.width / box_width) * (region.height / box_height) >= region.requirements.len() {
+        return true;
+    }
+    if first_fit(shapes_map, region) {
+        return true;
+    }
+
+    let mut counts = BTreeMap::new();
+    for &id in &region.requirements {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    let shapes = counts.len();
+    let mut problem = ExactCover::new(shapes, region.width * region.height);
+    let mut columns = Vec::new();
+    for (shape, (&id, &count)) in counts.iter().enumerate() {
+        problem.set_multiplicity(shape, count);
+        // Placements in reading order, so copies of a shape are placed in that order.
+        for y in 0..region.height {
+            for x in 0..region.width {
+                for variant in &shapes_map[&id] {
+                    if x + variant.width > region.width || y + variant.height > region.height {
+                        continue;
+                    }
+                    columns.clear();
+                    columns.push(shape);
+                    columns.extend(variant.points.iter().map(|p| {
+                        shapes + (y + p.y() as usize) * region.width + x + p.x() as usize
+                    }));
+                    problem.add_row(&columns);
+                }
+            }
+        }
+    }
+    problem.first_solution().is_some()
+}
+
+aoc_test!(part1: 2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcuts_and_search() {
+        // The first region has room for every bounding box, the second needs
+        // the pieces to interlock, and the third is short of area.
+        let input = "0:\n###\n#..\n\n69x2: 17\n68x2: 34\n69x2: 35\n";
+        let (shapes, regions) = parse_input(input).unwrap();
+        assert_eq!(solve(&shapes, &regions), 2);
+    }
+
+    #[test]
+    fn test_first_fit() {
+        // Two L shapes fill a 2x4 box once one is turned around, two T shapes
+        // never fit in a 3x3 box.
+        let (shapes, regions) = parse_input("0:\n##\n#.\n#.\n\n2x4: 2\n").unwrap();
+        assert!(first_fit(&shapes, &regions[0]));
+        let (shapes, regions) = parse_input("0:\n###\n.#.\n\n3x3: 2\n").unwrap();
+        assert!(!first_fit(&shapes, &regions[0]));
+        assert!(!fits(&shapes, &regions[0]));
+
+        let wide = format!("0:\n{}\n\n1x1: 0\n", "#".repeat(65));
+        let err = parse_input(&wide).err().unwrap();
+        assert_eq!(err.line, Some(1));
+    }
+
+    #[test]
+    fn test_missing_shape() {
+        let err = parse_input("0:\n##\n\n4x4: 1 0\n4x4: 0 2\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 5: no shape 1");
+    }
+}
@@ -1,36 +1,774 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
 use std::env;
-use syn::{ItemFn, parse_macro_input};
+use std::path::PathBuf;
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    Expr, ExprLit, ExprUnary, Ident, ItemFn, Lit, LitInt, ReturnType, Token, Type, UnOp,
+    parse_macro_input,
+};
+
+/// Which input file of the day `aoc_input!` embeds.
+enum InputKind {
+    /// `input/XX.txt`
+    Puzzle,
+    /// `input/XX.example.txt` for 1, `input/XX.exampleN.txt` otherwise.
+    Example(u32),
+}
+
+impl Parse for InputKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self::Puzzle);
+        }
+        let ident: Ident = input.parse()?;
+        if ident != "example" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected `example` or `example = N`",
+            ));
+        }
+        if input.parse::<Option<Token![=]>>()?.is_none() {
+            return Ok(Self::Example(1));
+        }
+        let lit: LitInt = input.parse()?;
+        match lit.base10_parse()? {
+            0 => Err(syn::Error::new(lit.span(), "examples are numbered from 1")),
+            n => Ok(Self::Example(n)),
+        }
+    }
+}
 
 #[proc_macro]
-pub fn aoc_input(_item: TokenStream) -> TokenStream {
+pub fn aoc_input(item: TokenStream) -> TokenStream {
+    let kind = parse_macro_input!(item as InputKind);
+    TokenStream::from(input_tokens(&kind))
+}
+
+/// File name and absolute path of the input file selected by `kind`.
+fn input_file(kind: &InputKind) -> (String, PathBuf) {
     let pkg_name = env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME must be set");
     let day_str = pkg_name
         .strip_prefix("day")
         .expect("Package name must start with 'day'");
-    let path = format!("../../input/{}.txt", day_str);
-    let expanded = format!("include_str!(\"{}\")", path);
-    expanded.parse().unwrap()
+    let file_name = match *kind {
+        InputKind::Puzzle => format!("{day_str}.txt"),
+        InputKind::Example(1) => format!("{day_str}.example.txt"),
+        InputKind::Example(n) => format!("{day_str}.example{n}.txt"),
+    };
+    let path = input_dir().join(&file_name);
+    (file_name, path)
+}
+
+/// Expression embedding the input file selected by `kind`.
+fn input_tokens(kind: &InputKind) -> TokenStream2 {
+    let (file_name, path) = input_file(kind);
+
+    if path.is_file() {
+        let path = path.to_str().expect("Input path must be valid UTF-8");
+        quote! { include_str!(#path) }
+    } else if let InputKind::Example(_) = kind {
+        let msg = format!(
+            "missing example input `input/{file_name}` (expected at {})",
+            path.display()
+        );
+        quote! { ::core::compile_error!(#msg) }
+    } else if cfg!(feature = "allow-missing-input") {
+        quote! { "" }
+    } else {
+        let msg = format!(
+            "missing puzzle input `input/{file_name}` (expected at {}). \
+             Save your input there, or enable the `macros/allow-missing-input` feature \
+             to build without it and pass an input path at runtime.",
+            path.display()
+        );
+        quote! { ::core::compile_error!(#msg) }
+    }
+}
+
+/// Day number of the invoking `dayXX` package, if it is one.
+fn package_day() -> Option<u8> {
+    env::var("CARGO_PKG_NAME")
+        .ok()?
+        .strip_prefix("day")?
+        .parse()
+        .ok()
+}
+
+/// The workspace `input/` directory, next to the invoking package.
+fn input_dir() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set");
+    let manifest_dir = PathBuf::from(manifest_dir);
+    manifest_dir.parent().unwrap_or(&manifest_dir).join("input")
 }
 
+/// Options of `#[aoc_timed(runs = N, warmup = N)]`.
+struct TimedArgs {
+    runs: u32,
+    warmup: u32,
+}
+
+impl TimedArgs {
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("runs") {
+            let lit: LitInt = meta.value()?.parse()?;
+            self.runs = lit.base10_parse()?;
+            if self.runs == 0 {
+                return Err(syn::Error::new(lit.span(), "`runs` must be at least 1"));
+            }
+        } else if meta.path.is_ident("warmup") {
+            self.warmup = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+        } else {
+            return Err(meta.error("expected `runs` or `warmup`"));
+        }
+        Ok(())
+    }
+}
+
+/// Times the annotated function and prints the elapsed time.
+///
+/// With `runs = N` (and optionally `warmup = N`) the body is executed `warmup`
+/// extra times first and then `runs` times, printing min/median/mean/stddev.
+/// The value of the last run is returned, so side effects such as printing
+/// repeat on every run.
+///
+/// With the `alloc-stats` feature of `common`, the peak heap usage and number of
+/// allocations of a run (the worst one for `runs = N`) are printed as well.
+///
+/// Phases timed with [`aoc_phase!`] inside the body are printed as a breakdown
+/// after the total. The generated code reports through `common::timing`, so the
+/// crate using it must depend on `common`, and the `AOC_TIMING` environment
+/// variable selects the output (`stdout`, `stderr`, `json` or `off`).
 #[proc_macro_attribute]
-pub fn aoc_timed(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn aoc_timed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = TimedArgs { runs: 1, warmup: 0 };
+    let parser = syn::meta::parser(|meta| args.parse_meta(meta));
+    parse_macro_input!(attr with parser);
     let input_fn = parse_macro_input!(item as ItemFn);
-    let _fn_name = &input_fn.sig.ident;
     let fn_body = &input_fn.block;
     let fn_vis = &input_fn.vis;
     let fn_sig = &input_fn.sig;
+    let day = match package_day() {
+        Some(day) => quote! { Some(#day) },
+        None => quote! { None },
+    };
+
+    let expanded = if args.runs == 1 && args.warmup == 0 {
+        quote! {
+            #fn_vis #fn_sig {
+                let start = std::time::Instant::now();
+                let (result, alloc) = ::common::alloc::measure(|| #fn_body);
+                let duration = start.elapsed();
+                ::common::timing::report_with_alloc(#day, "total", &mut [duration], alloc);
+                ::common::timing::report_phases(#day);
+                result
+            }
+        }
+    } else {
+        let TimedArgs { runs, warmup } = args;
+        quote! {
+            #fn_vis #fn_sig {
+                #[allow(unused_mut)]
+                let mut body = || #fn_body;
+                for _ in 0..#warmup {
+                    let _ = body();
+                }
+                ::common::timing::take_phases();
+                let mut samples = Vec::with_capacity(#runs as usize);
+                let mut result = None;
+                let mut alloc: Option<::common::alloc::AllocStats> = None;
+                for _ in 0..#runs {
+                    let start = std::time::Instant::now();
+                    let (value, stats) = ::common::alloc::measure(&mut body);
+                    samples.push(start.elapsed());
+                    result = Some(value);
+                    alloc = stats.map(|s| alloc.map_or(s, |a| a.max(s)));
+                }
+                ::common::timing::report_with_alloc(#day, "total", &mut samples, alloc);
+                ::common::timing::report_phases(#day);
+                result.expect("at least one run")
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// `aoc_phase!(name, expr)` evaluates `expr` and records its duration as phase `name`.
+struct PhaseArgs {
+    name: Ident,
+    expr: Expr,
+}
+
+impl Parse for PhaseArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let expr = input.parse()?;
+        Ok(Self { name, expr })
+    }
+}
 
+/// Times a single phase (e.g. `parse`, `part1`, `part2`) of a solution.
+///
+/// `aoc_phase!(parse, parse_input(&input))` evaluates to the value of the
+/// expression. Inside a `#[aoc_timed]` function the recorded phases are printed
+/// as a breakdown next to the total duration.
+#[proc_macro]
+pub fn aoc_phase(item: TokenStream) -> TokenStream {
+    let PhaseArgs { name, expr } = parse_macro_input!(item as PhaseArgs);
+    let name = name.to_string();
+    let expanded = quote! {
+        ::common::timing::phase(#name, || #expr)
+    };
+    TokenStream::from(expanded)
+}
+
+/// Whether `ty` is spelled `Result<...>`, possibly with a path.
+fn is_result(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "Result"))
+}
+
+/// Type of the value returned by a function: `T` for `-> T` and `-> Result<T, E>`,
+/// and whether it is wrapped in a `Result`.
+fn returned_type(sig: &syn::Signature) -> syn::Result<(&Type, bool)> {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return Err(syn::Error::new_spanned(sig, "expected a return type"));
+    };
+    if !is_result(ty) {
+        return Ok((ty, false));
+    }
+    if let Type::Path(p) = &**ty
+        && let Some(last) = p.path.segments.last()
+        && let syn::PathArguments::AngleBracketed(args) = &last.arguments
+        && let Some(syn::GenericArgument::Type(ok)) = args.args.first()
+    {
+        return Ok((ok, true));
+    }
+    Err(syn::Error::new_spanned(ty, "expected `Result<T, E>`"))
+}
+
+/// `Result<_, AocError>` of calling `call`, converting the error of a fallible function.
+fn result_tokens(call: TokenStream2, fallible: bool) -> TokenStream2 {
+    if fallible {
+        quote! { #call.map_err(::common::error::AocError::from) }
+    } else {
+        quote! { ::std::result::Result::Ok(#call) }
+    }
+}
+
+/// Registers the annotated function as the parser of this day.
+///
+/// The function takes the raw input and returns the parsed value `T`, or
+/// `Result<T, E>` with `E` convertible to `common::error::AocError`. It must be
+/// defined at the crate root. The input is parsed once, and the value is shared
+/// by the parts registered with [`aoc_part`], which run on it in order.
+///
+/// Also defines the unit struct `DayXX` implementing `common::Solution` with
+/// this function as its parse step.
+#[proc_macro_attribute]
+pub fn aoc_parse(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aoc_parse` takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let Some(day) = package_day() else {
+        return syn::Error::new_spanned(
+            &input_fn.sig,
+            "`aoc_parse` must be used in a dayXX package",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let (parsed, fallible) = match returned_type(&input_fn.sig) {
+        Ok(returned) => returned,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let fn_name = &input_fn.sig.ident;
+    let name = fn_name.to_string();
+    let wrapper = format_ident!("__aoc_parse_{}", fn_name);
+    let solution = format_ident!("Day{:02}", day);
+    let doc = format!("Day {day}, parsed by [`{name}`] and solved by its `#[aoc_part]` functions.");
+    let parse = result_tokens(quote! { #fn_name(input) }, fallible);
     let expanded = quote! {
-        #fn_vis #fn_sig {
-            let start = std::time::Instant::now();
-            let result = (|| #fn_body)();
-            let duration = start.elapsed();
-            println!("[Duration] {:.2?}", duration);
-            result
+        #input_fn
+
+        #[doc = #doc]
+        pub struct #solution;
+
+        impl ::common::Solution for #solution {
+            const DAY: u8 = #day;
+
+            type Parsed = #parsed;
+
+            fn parse(input: &str) -> ::std::result::Result<#parsed, ::common::error::AocError> {
+                #parse
+            }
+        }
+
+        #[doc(hidden)]
+        fn #wrapper(
+            input: &str,
+        ) -> ::std::result::Result<::std::boxed::Box<dyn ::std::any::Any>, ::common::error::AocError> {
+            let parsed = <#solution as ::common::Solution>::parse(input)?;
+            ::std::result::Result::Ok(::std::boxed::Box::new(parsed))
+        }
+
+        ::common::registry::inventory::submit! {
+            ::common::registry::Day {
+                day: #day,
+                name: #name,
+                parse: #wrapper,
+            }
         }
     };
+    TokenStream::from(expanded)
+}
+
+/// Registers the annotated function as the solver of part `n` of this day.
+///
+/// The function takes the value parsed by the day's [`aoc_parse`] function by
+/// reference (`&T`, `&mut T` or anything `&mut T` coerces to, such as a slice)
+/// and returns the answer, or `Result<A, E>` with `E` convertible to
+/// `common::error::AocError`; errors are reported with the day by the runner.
+///
+/// The function becomes `common::SolvePart<n>` of the `DayXX` struct defined by
+/// [`aoc_parse`]. Registered parts are collected in `common::registry` and run
+/// by the `main` generated with [`aoc_main!`].
+#[proc_macro_attribute]
+pub fn aoc_part(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(attr as LitInt);
+    let part: u8 = match lit.base10_parse() {
+        Ok(0) => {
+            return syn::Error::new(lit.span(), "parts are numbered from 1")
+                .to_compile_error()
+                .into();
+        }
+        Ok(part) => part,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let Some(day) = package_day() else {
+        return syn::Error::new_spanned(
+            &input_fn.sig,
+            "`aoc_part` must be used in a dayXX package",
+        )
+        .to_compile_error()
+        .into();
+    };
 
+    let (answer, fallible) = match returned_type(&input_fn.sig) {
+        Ok(returned) => returned,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let fn_name = &input_fn.sig.ident;
+    let name = fn_name.to_string();
+    let wrapper = format_ident!("__aoc_part{}_{}", part, fn_name);
+    let solution = format_ident!("Day{:02}", day);
+    let solve = result_tokens(quote! { #fn_name(parsed) }, fallible);
+    let expanded = quote! {
+        #input_fn
+
+        impl ::common::SolvePart<#part> for crate::#solution {
+            type Answer = #answer;
+
+            fn solve(
+                parsed: &mut <Self as ::common::Solution>::Parsed,
+            ) -> ::std::result::Result<#answer, ::common::error::AocError> {
+                #solve
+            }
+        }
+
+        #[doc(hidden)]
+        fn #wrapper(
+            parsed: &mut dyn ::std::any::Any,
+        ) -> ::std::result::Result<::std::string::String, ::common::error::AocError> {
+            let parsed = parsed
+                .downcast_mut::<<crate::#solution as ::common::Solution>::Parsed>()
+                .expect("value parsed by the parser of the same day");
+            <crate::#solution as ::common::SolvePart<#part>>::solve(parsed)
+                .map(|answer| answer.to_string())
+        }
+
+        ::common::registry::inventory::submit! {
+            ::common::registry::Part {
+                day: #day,
+                part: #part,
+                name: #name,
+                solve: #wrapper,
+            }
+        }
+    };
     TokenStream::from(expanded)
 }
+
+/// Generates a `main` that parses the input with the [`aoc_parse`] function of
+/// this day and runs every part registered with [`aoc_part`].
+///
+/// The input is embedded like `aoc_input!()` and can be overridden at runtime
+/// (see `common::input::load`). Output and timing are the same for every day,
+/// and answers are checked against `input/answers.toml` (see `common::answers`).
+#[proc_macro]
+pub fn aoc_main(item: TokenStream) -> TokenStream {
+    if !item.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aoc_main!` takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let Some(day) = package_day() else {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aoc_main!` must be used in a dayXX package",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let lib = format_ident!("day{:02}", day);
+    let input = input_tokens(&InputKind::Puzzle);
+    let input_dir = input_dir();
+    let input_dir = input_dir.to_str().expect("Input path must be valid UTF-8");
+    let expanded = quote! {
+        // Link the library so its registered parts are part of the binary.
+        use #lib as _;
+
+        fn main() -> ::std::process::ExitCode {
+            ::common::registry::main(#day, #input, #input_dir)
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Arguments of `aoc_test!`: the input to test against, then `partN: answer` pairs.
+struct TestArgs {
+    kind: InputKind,
+    answers: Vec<(u8, Expr)>,
+}
+
+impl Parse for TestArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut kind = InputKind::Example(1);
+        if input.peek(Ident) && !input.peek2(Token![:]) {
+            let ident: Ident = input.fork().parse()?;
+            kind = if ident == "input" {
+                input.parse::<Ident>()?;
+                InputKind::Puzzle
+            } else {
+                input.parse()?
+            };
+            input.parse::<Token![,]>()?;
+        }
+
+        let mut answers = Vec::new();
+        for answer in Punctuated::<Answer, Token![,]>::parse_terminated(input)? {
+            answers.push((answer.part, answer.expected));
+        }
+        if answers.is_empty() {
+            return Err(input.error("expected at least one `partN: answer`"));
+        }
+        Ok(Self { kind, answers })
+    }
+}
+
+/// `partN: expected` in `aoc_test!`.
+struct Answer {
+    part: u8,
+    expected: Expr,
+}
+
+impl Parse for Answer {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        let part = ident
+            .to_string()
+            .strip_prefix("part")
+            .and_then(|n| n.parse().ok())
+            .filter(|&n| n != 0)
+            .ok_or_else(|| syn::Error::new(ident.span(), "expected `partN`, e.g. `part1`"))?;
+        input.parse::<Token![:]>()?;
+        let expected = input.parse()?;
+        Ok(Self { part, expected })
+    }
+}
+
+/// Expected answer as compared by `common::registry::check`.
+///
+/// Integer literals are passed as their digits so answers that do not fit an
+/// `i32` need no suffix.
+fn answer_tokens(expected: &Expr) -> TokenStream2 {
+    match int_literal(expected) {
+        Some(digits) => quote! { #digits },
+        None => quote! { #expected },
+    }
+}
+
+/// Digits of an integer literal such as `42` or `-42`.
+fn int_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => Some(lit.base10_digits().to_string()),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr)
+            .filter(|digits| !digits.starts_with('-'))
+            .map(|digits| format!("-{digits}")),
+        _ => None,
+    }
+}
+
+/// Generates tests checking the parts registered with [`aoc_part`] against known answers.
+///
+/// `aoc_test!(part1: 13, part2: 43)` tests against `input/XX.example.txt`,
+/// `aoc_test!(example = 2, part2: 2)` against `input/XX.example2.txt`. Answers
+/// are compared by their `Display` output.
+///
+/// `aoc_test!(input, part1: ..., part2: ...)` adds regression tests against the
+/// real puzzle input. That file is read when the test runs and the test is
+/// skipped if it does not exist, so they pass on checkouts without inputs.
+///
+/// The tests are generated in a `#[cfg(test)]` module named after the input
+/// (`aoc_example`, `aoc_example2` or `aoc_input`) with one test per part.
+#[proc_macro]
+pub fn aoc_test(item: TokenStream) -> TokenStream {
+    let TestArgs { kind, answers } = parse_macro_input!(item as TestArgs);
+    let Some(day) = package_day() else {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aoc_test!` must be used in a dayXX package",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let module = match kind {
+        InputKind::Puzzle => format_ident!("aoc_input"),
+        InputKind::Example(1) => format_ident!("aoc_example"),
+        InputKind::Example(n) => format_ident!("aoc_example{}", n),
+    };
+    let tests = answers.iter().map(|(part, expected)| {
+        let name = format_ident!("part{}", part);
+        let expected = answer_tokens(expected);
+        let check = match kind {
+            InputKind::Puzzle => {
+                let (_, path) = input_file(&kind);
+                let path = path.to_str().expect("Input path must be valid UTF-8");
+                quote! { ::common::registry::check_file(#day, #part, #path, #expected) }
+            }
+            InputKind::Example(_) => {
+                let input = input_tokens(&kind);
+                quote! { ::common::registry::check(#day, #part, #input, #expected) }
+            }
+        };
+        quote! {
+            #[test]
+            fn #name() {
+                #check;
+            }
+        }
+    });
+
+    let expanded = quote! {
+        #[cfg(test)]
+        mod #module {
+            #(#tests)*
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Part of a `#[aoc(format = "...")]` pattern.
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Splits a format pattern into literals and `{field}` placeholders; `{{` and `}}` escape braces.
+fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if name.is_empty() || name.contains('{') {
+                    return Err(format!("invalid placeholder `{{{name}}}`"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if matches!(segments.last(), Some(Segment::Field(_))) {
+                    return Err(format!(
+                        "placeholder `{{{name}}}` must be separated from the previous one by literal text"
+                    ));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Value of `#[aoc(key = "...")]` among `attrs`, if present.
+fn aoc_attr(attrs: &[syn::Attribute], key: &str) -> syn::Result<Option<syn::LitStr>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{key} = \"...\"`")))
+            }
+        })?;
+    }
+    Ok(value)
+}
+
+/// Derives `common::parse::AocParse` and `FromStr` from a line format.
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc(format = "{src}: {dests}")]
+/// struct Connections {
+///     src: String,
+///     #[aoc(sep = " ")]
+///     dests: Vec<String>,
+/// }
+/// ```
+///
+/// Each `{field}` placeholder (`{0}` for tuple structs) takes the text up to
+/// the literal that follows it, or the rest of the line, and parses it with
+/// `FromStr`. Fields marked `#[aoc(sep = "...")]` are lists split on `sep`.
+/// Every field must appear exactly once, and placeholders must be separated by
+/// literal text. Errors report the column and, through `parse_lines`, the line.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    match aoc_parse_impl(&input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn aoc_parse_impl(input: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`AocParse` can only be derived for structs",
+        ));
+    };
+    let Some(format) = aoc_attr(&input.attrs, "format")? else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[aoc(format = \"...\")]`",
+        ));
+    };
+    let segments =
+        parse_format(&format.value()).map_err(|msg| syn::Error::new(format.span(), msg))?;
+
+    // (placeholder name, member, type, list separator) of every field.
+    let mut fields = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let (name, member) = match &field.ident {
+            Some(ident) => (ident.to_string(), syn::Member::Named(ident.clone())),
+            None => (i.to_string(), syn::Member::Unnamed(i.into())),
+        };
+        let sep = aoc_attr(&field.attrs, "sep")?;
+        fields.push((name, member, &field.ty, sep));
+    }
+
+    let mut steps = Vec::new();
+    let mut used = vec![false; fields.len()];
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => steps.push(quote! { scanner.literal(#literal)?; }),
+            Segment::Field(name) => {
+                let Some(index) = fields.iter().position(|f| f.0 == *name) else {
+                    return Err(syn::Error::new(
+                        format.span(),
+                        format!("no field named `{name}`"),
+                    ));
+                };
+                if std::mem::replace(&mut used[index], true) {
+                    return Err(syn::Error::new(
+                        format.span(),
+                        format!("field `{name}` appears more than once"),
+                    ));
+                }
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(literal)) => quote! { Some(#literal) },
+                    _ => quote! { None },
+                };
+                let var = format_ident!("__{}", name);
+                let ty = fields[index].2;
+                let value = match &fields[index].3 {
+                    Some(sep) => quote! { scanner.list(#name, #until, #sep)? },
+                    None => quote! { scanner.field(#name, #until)? },
+                };
+                steps.push(quote! { let #var: #ty = #value; });
+            }
+        }
+    }
+    if let Some(index) = used.iter().position(|&used| !used) {
+        return Err(syn::Error::new(
+            format.span(),
+            format!("field `{}` does not appear in the format", fields[index].0),
+        ));
+    }
+
+    let inits = fields.iter().map(|(name, member, _, _)| {
+        let var = format_ident!("__{}", name);
+        quote! { #member: #var }
+    });
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let format = format.value();
+    Ok(quote! {
+        impl #impl_generics ::common::parse::AocParse for #ident #ty_generics #where_clause {
+            const FORMAT: &'static str = #format;
+
+            fn parse_line(line: &str) -> ::core::result::Result<Self, ::common::parse::ParseError> {
+                let mut scanner = ::common::parse::Scanner::new(line);
+                #(#steps)*
+                scanner.finish()?;
+                Ok(Self { #(#inits),* })
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::common::parse::ParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as ::common::parse::AocParse>::parse_line(s)
+            }
+        }
+    })
+}
@@ -0,0 +1,94 @@
+//! Expands `#[aoc_timed(runs, warmup)]` and `aoc_phase!` and checks the reports
+//! they print, in a child process so the output can be captured.
+
+use macros::{aoc_phase, aoc_timed};
+use std::env;
+use std::process::Command;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Set in the child process started by [`run_child`].
+const CHILD: &str = "AOC_TIMED_TEST_CHILD";
+
+static CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[aoc_timed(runs = 3, warmup = 1)]
+fn solve() -> u64 {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    let numbers: Vec<u64> = aoc_phase!(parse, (1..=100).collect());
+    aoc_phase!(part1, numbers.iter().sum())
+}
+
+/// Runs `solve` when started by [`run_child`], and does nothing otherwise.
+#[test]
+fn child() {
+    if env::var_os(CHILD).is_none() {
+        return;
+    }
+    assert_eq!(solve(), 5050);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 4, "1 warmup and 3 runs");
+}
+
+/// Stdout and stderr of [`child`] run with `AOC_TIMING` set to `timing`.
+fn run_child(timing: &str) -> (String, String) {
+    let exe = env::current_exe().expect("path of the test binary");
+    let output = Command::new(exe)
+        .args(["child", "--exact", "--nocapture"])
+        .env(CHILD, "1")
+        .env("AOC_TIMING", timing)
+        .output()
+        .expect("test binary runs");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stdout}\n{stderr}");
+    (stdout, stderr)
+}
+
+#[test]
+fn test_text_report() {
+    let (stdout, _) = run_child("stdout");
+    // The first report follows `test child ... ` on the same line.
+    let reports: Vec<_> = stdout
+        .lines()
+        .filter_map(|line| line.find('[').map(|start| &line[start..]))
+        .collect();
+    // Heap usage is reported as well with the `alloc-stats` feature of `common`.
+    let memory = reports.iter().filter(|line| line.starts_with("[Memory] "));
+    assert_eq!(
+        memory.count(),
+        usize::from(common::alloc::enabled()),
+        "{stdout}"
+    );
+    let lines: Vec<_> = reports
+        .into_iter()
+        .filter(|line| line.starts_with("[Duration] ") || line.starts_with("[Phase] "))
+        .collect();
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[0].starts_with("[Duration] min "), "{}", lines[0]);
+    // Phases are only recorded for the timed runs, not the warmup.
+    assert!(lines[1].starts_with("[Phase] parse: min "), "{}", lines[1]);
+    assert!(lines[2].starts_with("[Phase] part1: min "), "{}", lines[2]);
+    assert!(lines.iter().all(|line| line.ends_with("(3 runs)")));
+}
+
+#[test]
+fn test_json_report() {
+    let (stdout, stderr) = run_child("json");
+    assert!(!stdout.contains("[Duration]"), "{stdout}");
+    let lines: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .collect();
+    assert_eq!(lines.len(), 3, "{stderr}");
+    for (line, phase) in lines.iter().zip(["total", "parse", "part1"]) {
+        let prefix = format!(r#"{{"day":null,"phase":"{phase}","runs":3,"ns":"#);
+        assert!(line.starts_with(&prefix), "{line}");
+        assert!(line.contains(r#","stddev_ns":"#), "{line}");
+        // Heap usage of the `alloc-stats` feature is part of the total.
+        let alloc = line.contains(r#","peak_bytes":"#) && line.contains(r#","allocations":"#);
+        assert_eq!(
+            alloc,
+            phase == "total" && common::alloc::enabled(),
+            "{line}"
+        );
+    }
+}